@@ -2,39 +2,460 @@
 
 #[ink::contract]
 mod inky_todo {
-    use ink::storage::Mapping;
-    use ink::prelude::string::{String, ToString};
-    
+    use ink::env::call::{build_call, ExecutionInput, Selector};
+    use ink::storage::{Lazy, Mapping};
+    use ink::prelude::string::String;
+    use ink::prelude::vec::Vec;
+
+    /// Version of the contract's message and event schema, bumped on breaking changes
+    pub const CONTRACT_VERSION: u32 = 3;
+
+    /// Name reported by `contract_info`
+    pub const CONTRACT_NAME: &str = "inky_todo";
+
+    /// Release reported by `contract_info`, bumped with every deployment
+    pub const CONTRACT_RELEASE: &str = "0.1.0";
+
+    /// Maximum number of todos returned by a single listing call
+    pub const MAX_LIST_LIMIT: u32 = 100;
+
+    /// Maximum length of a todo title in bytes
+    pub const MAX_TITLE_LEN: u32 = 100;
+
+    /// Maximum length of a todo description in bytes
+    pub const MAX_DESCRIPTION_LEN: u32 = 500;
+
+    /// Maximum number of todos created by a single batch call
+    pub const MAX_BATCH_SIZE: u32 = 50;
+
+    /// Maximum number of tags on a single todo
+    pub const MAX_TAGS: u32 = 10;
+
+    /// Maximum length of a tag in bytes
+    pub const MAX_TAG_LEN: u32 = 32;
+
+    /// Maximum number of checklist items that can ever be added to a single todo.
+    /// Subtask IDs are not reused, so removing one does not free a slot.
+    pub const MAX_SUBTASKS: u32 = 20;
+
+    /// Maximum length of a comment in bytes
+    pub const MAX_COMMENT_LEN: u32 = 280;
+
+    /// Maximum number of comments on a single todo
+    pub const MAX_COMMENTS: u32 = 50;
+
+    /// Minimum length in bytes of a `search_my_todos` needle
+    pub const MIN_SEARCH_LEN: u32 = 3;
+
+    /// Largest packed `0xRRGGBB` value accepted as a todo color
+    pub const MAX_COLOR: u32 = 0xFF_FFFF;
+
+    /// Maximum number of todos a single todo can be blocked by
+    pub const MAX_DEPENDENCIES: u32 = 10;
+
+    /// Computation time (in picoseconds) the notifier may spend handling a completion
+    pub const NOTIFY_REF_TIME_LIMIT: u64 = 5_000_000_000;
+
+    /// Proof size (in bytes) the notifier may use while handling a completion
+    pub const NOTIFY_PROOF_SIZE_LIMIT: u64 = 256 * 1024;
+
+    /// Default number of todos a single account may hold
+    pub const DEFAULT_MAX_TODOS_PER_ACCOUNT: u32 = 1000;
+
+    /// Highest value `set_max_todos` accepts, keeping each `todos_by_owner` list
+    /// (4 bytes per ID) well below the 16 KiB limit on a single storage value
+    pub const MAX_TODOS_PER_ACCOUNT: u32 = 2000;
+
     /// Represents the status of a todo item
     #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
     pub enum TodoStatus {
         Pending,
         Completed,
+        Cancelled,
+        /// Being worked on; declared last so stored statuses keep their encoding
+        InProgress,
+    }
+
+    impl TodoStatus {
+        /// Whether a todo with this status still counts as open
+        pub fn is_active(&self) -> bool {
+            matches!(self, TodoStatus::Pending | TodoStatus::InProgress)
+        }
+    }
+
+    /// The single source of truth for which status changes are allowed:
+    ///
+    /// ```text
+    /// Pending    -> InProgress | Completed | Cancelled
+    /// InProgress -> Pending | Completed | Cancelled
+    /// ```
+    ///
+    /// `Cancelled` and `Completed` cannot move back through `update_todo_status`;
+    /// `reopen_todo` is the sanctioned way back to `Pending`. Setting the current
+    /// status again is a no-op.
+    pub fn is_valid_transition(from: &TodoStatus, to: &TodoStatus) -> bool {
+        matches!(
+            (from, to),
+            (TodoStatus::Pending, TodoStatus::InProgress)
+                | (TodoStatus::Pending, TodoStatus::Completed)
+                | (TodoStatus::Pending, TodoStatus::Cancelled)
+                | (TodoStatus::InProgress, TodoStatus::Pending)
+                | (TodoStatus::InProgress, TodoStatus::Completed)
+                | (TodoStatus::InProgress, TodoStatus::Cancelled)
+        )
+    }
+
+    /// Represents the importance of a todo item, ordered from lowest to highest
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, scale::Encode, scale::Decode, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum Priority {
+        Low,
+        Medium,
+        High,
     }
 
-    /// Represents a todo item
+    /// Represents a todo item.
+    ///
+    /// Todos are stored in the `todos` mapping wrapped in `StoredTodo`. Changing the
+    /// fields of this struct requires freezing the current layout under its `StoredTodo`
+    /// variant and adding a new variant, so entries written by an older build keep decoding.
     #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
     pub struct Todo {
         pub id: u32,
+        pub owner: AccountId,
         pub title: String,
         pub description: String,
         pub status: TodoStatus,
+        pub priority: Priority,
+        /// Block timestamp at creation
+        pub created_at: u64,
+        /// Block timestamp of the last modification
+        pub updated_at: u64,
+        /// Optional deadline as a block timestamp in milliseconds
+        pub due_date: Option<u64>,
+        /// Block timestamp at which the todo was last completed
+        pub completed_at: Option<u64>,
+        /// Archived todos are kept in storage but hidden from default listings
+        pub archived: bool,
+        /// Free-form labels such as "work" or "home"
+        pub tags: Vec<String>,
+        /// Manual sort key, defaulting to the ID so new todos go last
+        pub position: u32,
+        /// Interval in milliseconds after which a completed todo comes back as a fresh copy
+        pub recurrence: Option<u64>,
+        /// Account the todo is assigned to. The assignee may change the status,
+        /// while every other change stays reserved to the owner.
+        pub assignee: Option<AccountId>,
+        /// Deposit paid at creation, refunded to the owner on completion. If the todo is
+        /// cancelled or deleted first it is forfeited when the owner did so and refunded otherwise.
+        pub stake: Balance,
+        /// Incremented on every write so clients can detect concurrent changes
+        pub rev: u32,
+        /// IDs of the todos that must be completed before this one can be
+        pub blocked_by: Vec<u32>,
+        /// Pinned by the owner to the top of `list_my_todos_ordered`
+        pub favorite: bool,
+        /// Display color packed as `0xRRGGBB`
+        pub color: Option<u32>,
+        /// Block timestamp in milliseconds before which the todo cannot be completed
+        pub not_before: Option<u64>,
+    }
+
+    impl Todo {
+        /// Whether the todo still counts as open
+        pub fn is_active(&self) -> bool {
+            !self.archived && self.status.is_active()
+        }
+    }
+
+    /// A todo as kept in storage, tagged with the layout it was written in.
+    /// New layouts are appended as new variants; existing ones must never change.
+    ///
+    /// Versioning starts with `V1`. Builds before it stored bare, untagged `Todo`
+    /// values that this type cannot tell apart from tagged ones, so such deployments
+    /// need a fresh instantiation (e.g. via `export_all`/`import_todos`), not `set_code`.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum StoredTodo {
+        V1(Todo),
+    }
+
+    impl StoredTodo {
+        /// Whether this entry is written in the latest layout
+        pub fn is_latest(&self) -> bool {
+            match self {
+                StoredTodo::V1(_) => true,
+            }
+        }
+    }
+
+    impl From<StoredTodo> for Todo {
+        fn from(stored: StoredTodo) -> Self {
+            match stored {
+                StoredTodo::V1(todo) => todo,
+            }
+        }
+    }
+
+    /// A checklist item belonging to a todo
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct SubTask {
+        pub id: u32,
+        pub text: String,
+        pub done: bool,
+    }
+
+    /// A note left on a todo
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct Comment {
+        pub author: AccountId,
+        pub text: String,
+        pub timestamp: u64,
+    }
+
+    /// Public read shape of a todo, leaving out the owner and internal bookkeeping
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct TodoView {
+        pub id: u32,
+        pub title: String,
+        pub status: TodoStatus,
+        pub due_date: Option<u64>,
+    }
+
+    impl From<Todo> for TodoView {
+        fn from(todo: Todo) -> Self {
+            Self {
+                id: todo.id,
+                title: todo.title,
+                status: todo.status,
+                due_date: todo.due_date,
+            }
+        }
     }
 
+    /// Criteria for `query`; every provided field must match and `None` fields are ignored
+    #[derive(Debug, Default, PartialEq, Eq, scale::Encode, scale::Decode, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct TodoFilter {
+        pub status: Option<TodoStatus>,
+        pub owner: Option<AccountId>,
+        pub priority: Option<Priority>,
+    }
+
+    /// Identity and state of the deployed contract as returned by `contract_info`
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct ContractInfo {
+        pub name: String,
+        pub version: String,
+        pub owner: AccountId,
+        pub paused: bool,
+        /// Number of todos currently stored
+        pub total_todos: u32,
+    }
+
+    /// Summary of an account's todos as returned by `get_statistics`
+    #[derive(Debug, Default, PartialEq, Eq, scale::Encode, scale::Decode, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct TodoStats {
+        pub total: u32,
+        pub pending: u32,
+        pub completed: u32,
+        pub cancelled: u32,
+        /// Open todos past their due date, also counted in `pending` or `in_progress`
+        pub overdue: u32,
+        pub in_progress: u32,
+    }
+
+    /// Errors that can occur when interacting with the contract
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// The title of a todo must not be empty
+        TitleEmpty,
+        /// The title exceeds `MAX_TITLE_LEN` bytes
+        TitleTooLong,
+        /// The description exceeds `MAX_DESCRIPTION_LEN` bytes
+        DescriptionTooLong,
+        /// No todo exists with the given ID
+        TodoNotFound,
+        /// The caller does not own the todo
+        NotOwner,
+        /// Too many items were passed in a single batch call
+        BatchTooLarge,
+        /// The todo is already archived
+        AlreadyArchived,
+        /// The todo is not archived
+        NotArchived,
+        /// Tags must not be empty
+        TagEmpty,
+        /// The tag exceeds `MAX_TAG_LEN` bytes
+        TagTooLong,
+        /// The todo already carries `MAX_TAGS` tags
+        TooManyTags,
+        /// The caller is not the contract owner
+        NotContractOwner,
+        /// Writes are disabled while the contract is paused
+        ContractPaused,
+        /// The zero account cannot be used here
+        ZeroAccount,
+        /// The caller already holds `max_todos_per_account` todos
+        AccountLimitReached,
+        /// Every todo ID has been handed out
+        IdSpaceExhausted,
+        /// The todo already belongs to the given account
+        SameOwner,
+        /// Subtask labels must not be empty
+        SubtaskEmpty,
+        /// `MAX_SUBTASKS` subtasks have already been added to the todo
+        TooManySubtasks,
+        /// No subtask exists with the given ID
+        SubtaskNotFound,
+        /// Comments must not be empty
+        CommentEmpty,
+        /// The comment exceeds `MAX_COMMENT_LEN` bytes
+        CommentTooLong,
+        /// The todo already carries `MAX_COMMENTS` comments
+        TooManyComments,
+        /// The caller is neither the owner nor the assignee of the todo
+        NotAssignee,
+        /// Less than the creation fee was transferred
+        InsufficientFee,
+        /// The contract does not hold enough withdrawable funds
+        InsufficientBalance,
+        /// The caller is not the proposed new contract owner
+        NotPendingOwner,
+        /// Swapping the contract code failed
+        UpgradeFailed,
+        /// The owner already has an active todo with this title
+        DuplicateTitle,
+        /// The todo was changed since the revision the caller read
+        StaleRevision,
+        /// A todo cannot depend on itself or on a todo that depends on it
+        InvalidDependency,
+        /// The todo already depends on `MAX_DEPENDENCIES` todos
+        TooManyDependencies,
+        /// A todo this one depends on is not completed yet
+        DependenciesIncomplete,
+        /// The contract already holds `max_active` open todos
+        ActiveLimitReached,
+        /// The color is larger than `MAX_COLOR`
+        InvalidColor,
+        /// The todo cannot be completed before its `not_before` time
+        TooEarly,
+        /// The allowlist is enabled and the caller is not on it
+        NotAllowed,
+        /// A mutating message was entered while an external call is in flight
+        ReentrantCall,
+        /// The contract already holds `max_total` todos
+        TooManyTodos,
+        /// A native token transfer out of the contract failed
+        TransferFailed,
+        /// The requested status change is not allowed by `is_valid_transition`
+        InvalidTransition,
+        /// The per-account limit exceeds `MAX_TODOS_PER_ACCOUNT`
+        LimitTooHigh,
+        /// A batch call was given no items
+        EmptyBatch,
+    }
+
+    /// Result type used by the contract messages
+    pub type Result<T> = core::result::Result<T, Error>;
+
     /// Defines the storage of your contract.
+    ///
+    /// Code deployed through `set_code` reuses this storage as-is, so new versions must
+    /// keep these fields, their order and their types and may only append new fields at
+    /// the end. `Todo` changes go through a new `StoredTodo` variant instead.
     #[ink(storage)]
     pub struct InkyTodo {
+        /// Administrator allowed to pause the contract
+        owner: AccountId,
+        /// Account proposed as the next owner, which still has to accept
+        pending_owner: Option<AccountId>,
+        /// Contract pinged through `on_todo_completed(todo_id, owner)` whenever a todo is completed
+        notifier: Option<AccountId>,
+        /// When set, every mutating message is rejected
+        paused: bool,
         next_id: u32,
-        todos: Mapping<u32, Todo>,
+        todos: Mapping<u32, StoredTodo>,
+        /// First and last live todo IDs, 0 when none are stored
+        first_id: u32,
+        last_id: u32,
+        /// Live todo IDs as a doubly linked list in ascending order. Mapping is not
+        /// iterable, so filters walk this list instead of loading one growing value.
+        next_live: Mapping<u32, u32>,
+        prev_live: Mapping<u32, u32>,
+        total_created: u32,
+        active_count: u32,
+        /// Number of todos currently stored; unlike `next_id` this drops on delete
+        todo_count: u32,
+        /// Number of live todos in each status
+        pending_count: u32,
+        completed_count: u32,
+        cancelled_count: u32,
+        /// Maximum number of todos a single account may hold
+        max_todos_per_account: u32,
+        /// Number of todos currently held by each account
+        todos_per_account: Mapping<AccountId, u32>,
+        /// IDs of the todos held by each account in the order they were received.
+        /// Each list is capped by `max_todos_per_account`, which never exceeds `MAX_TODOS_PER_ACCOUNT`.
+        todos_by_owner: Mapping<AccountId, Vec<u32>>,
+        /// Title and deletion timestamp of every hard-deleted todo. IDs are never
+        /// reused because `next_id` only grows, so entries are never overwritten.
+        tombstones: Mapping<u32, (String, u64)>,
+        /// Checklist items keyed by `(todo_id, subtask_id)`
+        subtasks: Mapping<(u32, u32), SubTask>,
+        /// Number of subtask IDs handed out per todo; IDs start at 1
+        subtask_counter: Mapping<u32, u32>,
+        /// Comment thread of each todo, oldest first
+        comments: Mapping<u32, Vec<Comment>>,
+        /// Sum of the stakes held by live todos
+        total_staked: Balance,
+        /// Stakes of todos their owners cancelled or deleted before completion
+        forfeited: Balance,
+        /// Fee charged per created todo to deter spam
+        create_fee: Balance,
+        /// Accounts allowed to update the status of and edit any todo
+        editors: Mapping<AccountId, bool>,
+        /// When set, an owner cannot create a todo whose title matches one of their active todos
+        dedupe: bool,
+        /// Todo last created under each `(owner, title hash)` while `dedupe` was on.
+        /// Entries may be stale after edits or reassignment and are re-checked on use.
+        title_ids: Mapping<(AccountId, Hash), u32>,
+        /// Number of live todos in `InProgress`, kept apart from the other status counters
+        /// since fields may only be appended
+        in_progress_count: u32,
+        /// Maximum number of open todos across all accounts, if any
+        max_active: Option<u32>,
+        /// When set, only accounts in `allowlist` may create todos
+        allowlist_enabled: bool,
+        allowlist: Mapping<AccountId, ()>,
+        /// Set while the notifier is being called. Kept `Lazy` so the flag hits storage
+        /// immediately and is visible to a re-entered call.
+        locked: Lazy<bool>,
+        /// Maximum number of live todos across all accounts, if any
+        max_total: Option<u32>,
     }
 
     /// Events 
+    #[ink(event)]
+    pub struct Instantiated {
+        #[ink(topic)]
+        owner: AccountId,
+    }
+
     #[ink(event)]
     pub struct TodoCreated {
         #[ink(topic)]
         todo_id: u32,
+        #[ink(topic)]
+        caller: AccountId,
         title: String,
     }
 
@@ -42,16 +463,150 @@ mod inky_todo {
     pub struct TodoUpdated {
         #[ink(topic)]
         todo_id: u32,
+        #[ink(topic)]
+        caller: AccountId,
+        previous_status: TodoStatus,
         new_status: TodoStatus,
     }
 
+    #[ink(event)]
+    pub struct TodoReopened {
+        #[ink(topic)]
+        todo_id: u32,
+        reason: String,
+    }
+
+    #[ink(event)]
+    pub struct TodoEdited {
+        #[ink(topic)]
+        todo_id: u32,
+        title_changed: bool,
+        desc_changed: bool,
+    }
+
+    #[ink(event)]
+    pub struct TodoArchived {
+        #[ink(topic)]
+        todo_id: u32,
+    }
+
+    #[ink(event)]
+    pub struct TodoRestored {
+        #[ink(topic)]
+        todo_id: u32,
+    }
+
+    #[ink(event)]
+    pub struct TodoReassigned {
+        #[ink(topic)]
+        todo_id: u32,
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct CommentAdded {
+        #[ink(topic)]
+        todo_id: u32,
+        #[ink(topic)]
+        author: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct TodoFavorited {
+        #[ink(topic)]
+        todo_id: u32,
+        favorite: bool,
+    }
+
+    #[ink(event)]
+    pub struct TodoAssigned {
+        #[ink(topic)]
+        todo_id: u32,
+        #[ink(topic)]
+        assignee: AccountId,
+    }
+
     #[ink(event)]
     pub struct TodoDeleted {
         #[ink(topic)]
         todo_id: u32,
+        #[ink(topic)]
+        caller: AccountId,
         title: String,
     }
 
+    #[ink(event)]
+    pub struct OwnershipTransferStarted {
+        #[ink(topic)]
+        previous: AccountId,
+        #[ink(topic)]
+        new: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct OwnershipTransferred {
+        #[ink(topic)]
+        previous: AccountId,
+        #[ink(topic)]
+        new: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct NotifyFailed {
+        #[ink(topic)]
+        todo_id: u32,
+        notifier: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct Withdrawn {
+        #[ink(topic)]
+        to: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct CodeUpgraded {
+        #[ink(topic)]
+        code_hash: Hash,
+    }
+
+    #[ink(event)]
+    pub struct Paused {
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct Unpaused {
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct AllowlistAdded {
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct AllowlistRemoved {
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    /// Full state of a todo after every write, so indexers don't need to query
+    /// `get_todo`. Kept separate from the lifecycle events to keep those small;
+    /// `deleted` marks the last snapshot of a removed todo.
+    #[ink(event)]
+    pub struct TodoSnapshot {
+        #[ink(topic)]
+        todo_id: u32,
+        todo: Todo,
+        deleted: bool,
+    }
+
     impl Default for InkyTodo {
         fn default() -> Self {
             Self::new()
@@ -62,87 +617,4206 @@ mod inky_todo {
         /// Constructor
         #[ink(constructor)]
         pub fn new() -> Self {
+            let owner = Self::env().caller();
+
+            // Emit event
+            Self::env().emit_event(Instantiated { owner });
+
             Self {
+                owner,
+                pending_owner: None,
+                notifier: None,
+                paused: false,
                 next_id: 1,
                 todos: Mapping::new(),
+                first_id: 0,
+                last_id: 0,
+                next_live: Mapping::new(),
+                prev_live: Mapping::new(),
+                total_created: 0,
+                active_count: 0,
+                todo_count: 0,
+                pending_count: 0,
+                completed_count: 0,
+                cancelled_count: 0,
+                max_todos_per_account: DEFAULT_MAX_TODOS_PER_ACCOUNT,
+                todos_per_account: Mapping::new(),
+                todos_by_owner: Mapping::new(),
+                tombstones: Mapping::new(),
+                subtasks: Mapping::new(),
+                subtask_counter: Mapping::new(),
+                comments: Mapping::new(),
+                total_staked: 0,
+                forfeited: 0,
+                create_fee: 0,
+                editors: Mapping::new(),
+                dedupe: false,
+                title_ids: Mapping::new(),
+                in_progress_count: 0,
+                max_active: None,
+                allowlist_enabled: false,
+                allowlist: Mapping::new(),
+                locked: Lazy::new(),
+                max_total: None,
             }
         }
 
-        /// Create a new todo item
-        #[ink(message)]
-        pub fn create_todo(&mut self, title: String, description: String) -> Result<u32, String> {
-        
-            let todo_id = self.next_id;
-            
-            // Validate input
-            if title.is_empty() {
-                return Err("Title cannot be empty".to_string());
+        /// Create a new todo item with `Medium` priority, an optional deadline, initial tags
+        /// and an optional color. Duplicate tags are dropped. At least `create_fee` must be transferred;
+        /// anything above the fee is held as the todo's stake.
+        #[ink(message, payable)]
+        pub fn create_todo(
+            &mut self,
+            title: String,
+            description: String,
+            due_date: Option<u64>,
+            tags: Vec<String>,
+            color: Option<u32>,
+        ) -> Result<u32> {
+            self.ensure_not_paused()?;
+            self.ensure_allowed()?;
+
+            let mut todo = self.draft_todo(title, description);
+            todo.due_date = due_date;
+            todo.tags = tags;
+            todo.color = color;
+            todo.stake = self.charge_fee(1)?;
+
+            self.insert_todo(todo)
+        }
+
+        /// Create a new todo item with the given priority.
+        /// Fee and stake are handled as in `create_todo`.
+        #[ink(message, payable)]
+        pub fn create_todo_with_priority(&mut self, title: String, description: String, priority: Priority) -> Result<u32> {
+            self.ensure_not_paused()?;
+            self.ensure_allowed()?;
+
+            let mut todo = self.draft_todo(title, description);
+            todo.priority = priority;
+            todo.stake = self.charge_fee(1)?;
+
+            self.insert_todo(todo)
+        }
+
+        /// Create several todos from `(title, description)` pairs in one call.
+        /// The whole batch is rejected if any title is invalid, so nothing is stored partially.
+        /// `create_fee` is due per item; as in `create_todo` any excess is held as stake,
+        /// split evenly across the items with the remainder on the first.
+        #[ink(message, payable)]
+        pub fn create_todos(&mut self, items: Vec<(String, String)>) -> Result<Vec<u32>> {
+            self.ensure_not_paused()?;
+            self.ensure_allowed()?;
+            // An empty batch owes no fee, so any value sent with it must not be kept
+            if items.is_empty() {
+                return Err(Error::EmptyBatch);
             }
+            let count = items.len() as u32;
+            let excess = self.charge_fee(count)?;
 
-            let todo = Todo {
-                id: todo_id,
-                title: title.clone(),
-                description,
-                status: TodoStatus::Pending,
-            };
+            if items.len() > MAX_BATCH_SIZE as usize {
+                return Err(Error::BatchTooLarge);
+            }
 
-            self.todos.insert(todo_id, &todo);
-            self.next_id = self.next_id.saturating_add(1);
+            // Validate the whole batch before touching storage
+            for (title, description) in &items {
+                Self::validate_title(title)?;
+                Self::validate_description(description)?;
+            }
+            self.ensure_capacity(self.env().caller(), items.len() as u32)?;
 
-            // Emit event
-            self.env().emit_event(TodoCreated {
-                todo_id,
-                title,
-            });
+            let mut ids = Vec::with_capacity(items.len());
+            for (index, (title, description)) in items.into_iter().enumerate() {
+                let mut todo = self.draft_todo(title, description);
+                todo.stake = Self::stake_share(excess, count, index);
+                ids.push(self.insert_todo(todo)?);
+            }
 
-            Ok(todo_id)
+            Ok(ids)
+        }
+
+        /// Same as `create_todos`, kept under the name integrators asked for
+        #[ink(message, payable)]
+        pub fn create_todos_batch(&mut self, items: Vec<(String, String)>) -> Result<Vec<u32>> {
+            self.create_todos(items)
         }
 
         /// Get a todo by ID
         #[ink(message)]
         pub fn get_todo(&self, todo_id: u32) -> Option<Todo> {
-            self.todos.get(todo_id)
+            self.load_todo(todo_id)
         }
 
-        /// Update a todo status
+        /// Get the public view of a todo by its ID
         #[ink(message)]
-        pub fn update_todo_status( &mut self, todo_id: u32, new_status: TodoStatus ) -> Result<(), String> {
-            
-            // Check if todo exists
-            let mut todo = self.todos.get(todo_id)
-                .ok_or("Todo not found")?;
+        pub fn get_todo_view(&self, todo_id: u32) -> Option<TodoView> {
+            self.load_todo(todo_id).map(TodoView::from)
+        }
 
-            // Update the todo
-            todo.status = new_status;
-            self.todos.insert(todo_id, &todo);
+        /// Check whether a todo exists without decoding it
+        #[ink(message)]
+        pub fn exists(&self, todo_id: u32) -> bool {
+            self.todos.contains(todo_id)
+        }
 
-            // Emit event
-            self.env().emit_event(TodoUpdated {
-                todo_id,
-                new_status: todo.status,
-            });
+        /// Get the title and deletion timestamp of a hard-deleted todo
+        #[ink(message)]
+        pub fn deleted_info(&self, todo_id: u32) -> Option<(String, u64)> {
+            self.tombstones.get(todo_id)
+        }
 
-            Ok(())
+        /// Get several todos at once, positionally aligned with `ids` (`None` for missing ones).
+        /// Only the first `MAX_LIST_LIMIT` IDs are looked up.
+        #[ink(message)]
+        pub fn get_many(&self, ids: Vec<u32>) -> Vec<Option<Todo>> {
+            ids.into_iter()
+                .take(MAX_LIST_LIMIT as usize)
+                .map(|id| self.load_todo(id))
+                .collect()
         }
 
-        /// Delete a todo
+        /// Update a todo status. Allowed for the owner, the assignee and editors.
         #[ink(message)]
-        pub fn delete_todo(&mut self, todo_id: u32) -> Result<(), String> {
-            // Check if todo exists
-            let todo = self.todos.get(todo_id)
-                .ok_or("Todo not found")?;
+        pub fn update_todo_status( &mut self, todo_id: u32, new_status: TodoStatus ) -> Result<()> {
+            self.ensure_not_paused()?;
 
-            // Remove from storage
-            self.todos.remove(todo_id);
+            // Check if todo exists and the caller may work on it
+            let todo = self.workable_todo(todo_id)?;
+
+            self.apply_status(todo, new_status)
+        }
+
+        /// Update a todo status like `update_todo_status`, but only if its `rev` still
+        /// equals `expected_rev`, i.e. nobody wrote to it since the caller read it
+        #[ink(message)]
+        pub fn update_todo_status_checked(
+            &mut self,
+            todo_id: u32,
+            new_status: TodoStatus,
+            expected_rev: u32,
+        ) -> Result<()> {
+            self.ensure_not_paused()?;
+
+            // Check if todo exists and the caller may work on it
+            let todo = self.workable_todo(todo_id)?;
+            if todo.rev != expected_rev {
+                return Err(Error::StaleRevision);
+            }
+
+            self.apply_status(todo, new_status)
+        }
+
+        /// Mark a todo as completed, with the same checks as `update_todo_status`
+        #[ink(message)]
+        pub fn complete_todo(&mut self, todo_id: u32) -> Result<()> {
+            self.update_todo_status(todo_id, TodoStatus::Completed)
+        }
+
+        /// Mark a todo as cancelled, with the same checks as `update_todo_status`
+        #[ink(message)]
+        pub fn cancel_todo(&mut self, todo_id: u32) -> Result<()> {
+            self.update_todo_status(todo_id, TodoStatus::Cancelled)
+        }
+
+        /// Flip a todo between `Pending` and `Completed` and return the resulting status;
+        /// `InProgress` todos are completed. Reopening a completed todo this way is a deliberate
+        /// exception to `is_valid_transition`; cancelled todos cannot be toggled.
+        #[ink(message)]
+        pub fn toggle_todo(&mut self, todo_id: u32) -> Result<TodoStatus> {
+            self.ensure_not_paused()?;
+
+            // Check if todo exists and the caller may work on it
+            let todo = self.workable_todo(todo_id)?;
+
+            let new_status = match todo.status {
+                TodoStatus::Pending | TodoStatus::InProgress => TodoStatus::Completed,
+                TodoStatus::Completed => TodoStatus::Pending,
+                TodoStatus::Cancelled => return Err(Error::InvalidTransition),
+            };
+            self.write_status(todo, new_status.clone())?;
+
+            Ok(new_status)
+        }
+
+        /// Move a completed or cancelled todo back to `Pending`, recording why for auditing.
+        /// Allowed for the owner, the assignee and editors.
+        #[ink(message)]
+        pub fn reopen_todo(&mut self, todo_id: u32, reason: String) -> Result<()> {
+            self.ensure_not_paused()?;
+
+            // Check if todo exists and the caller may work on it
+            let todo = self.workable_todo(todo_id)?;
+
+            if todo.status.is_active() {
+                return Err(Error::InvalidTransition);
+            }
+            self.write_status(todo, TodoStatus::Pending)?;
 
             // Emit event
-            self.env().emit_event(TodoDeleted {
-                todo_id,
-                title: todo.title,
-            });
+            self.env().emit_event(TodoReopened { todo_id, reason });
 
             Ok(())
         }
 
+        /// Set the status of several todos at once.
+        /// Missing IDs, todos the caller may not work on and changes that fail validation are
+        /// skipped; returns how many were updated. Failures past validation, such as a stake
+        /// refund that cannot be paid, abort the whole batch.
+        #[ink(message)]
+        pub fn bulk_update_status(&mut self, ids: Vec<u32>, new_status: TodoStatus) -> Result<u32> {
+            self.ensure_not_paused()?;
+
+            if ids.len() > MAX_BATCH_SIZE as usize {
+                return Err(Error::BatchTooLarge);
+            }
+
+            let mut updated: u32 = 0;
+            for todo_id in ids {
+                let Ok(todo) = self.workable_todo(todo_id) else {
+                    continue;
+                };
+                if todo.status == new_status
+                    || !is_valid_transition(&todo.status, &new_status)
+                    || self.check_status(&todo, &new_status).is_err()
+                {
+                    continue;
+                }
+                self.write_status(todo, new_status.clone())?;
+                updated = updated.saturating_add(1);
+            }
+
+            Ok(updated)
+        }
+
+        /// Cancel every open todo past its due date among IDs `start..start + limit`
+        /// and return how many were swept. `limit` is capped at `MAX_LIST_LIMIT`.
+        /// Anyone may sweep since deadlines are public; swept stakes are only forfeited
+        /// when the owner sweeps their own todo and are refunded otherwise.
+        #[ink(message)]
+        pub fn sweep_overdue(&mut self, start: u32, limit: u32) -> Result<u32> {
+            self.ensure_not_paused()?;
+
+            let start = start.max(1);
+            let end = start.saturating_add(limit.min(MAX_LIST_LIMIT)).min(self.next_id);
+
+            let mut swept: u32 = 0;
+            for todo_id in start..end {
+                let Some(todo) = self.load_todo(todo_id) else {
+                    continue;
+                };
+                if self.todo_is_overdue(&todo) {
+                    self.apply_status(todo, TodoStatus::Cancelled)?;
+                    swept = swept.saturating_add(1);
+                }
+            }
+
+            Ok(swept)
+        }
+
+        /// Update the title and description of a todo, leaving its status untouched
+        #[ink(message)]
+        pub fn update_todo_details(&mut self, todo_id: u32, title: String, description: String) -> Result<()> {
+            self.edit_todo(todo_id, Some(title), Some(description), None, None, None)
+        }
+
+        /// Edit a todo, only changing the fields that are provided. Allowed for the owner and editors.
+        /// An edit that changes nothing is not stored and emits no event.
+        #[ink(message)]
+        pub fn edit_todo(
+            &mut self,
+            todo_id: u32,
+            title: Option<String>,
+            description: Option<String>,
+            priority: Option<Priority>,
+            due_date: Option<u64>,
+            color: Option<u32>,
+        ) -> Result<()> {
+            self.ensure_not_paused()?;
+
+            // Check if todo exists and the caller may edit it
+            let mut todo = self.editable_todo(todo_id)?;
+
+            // Validate input
+            if let Some(title) = &title {
+                Self::validate_title(title)?;
+            }
+            if let Some(description) = &description {
+                Self::validate_description(description)?;
+            }
+            Self::validate_color(color)?;
+            let title_changed = title.as_ref().is_some_and(|title| *title != todo.title);
+            let title_taken = title.as_ref().is_some_and(|title| self.title_taken(todo.owner, title, todo_id));
+            if title_changed && todo.is_active() && title_taken {
+                return Err(Error::DuplicateTitle);
+            }
+
+            // Update the todo, leaving it untouched when every value is already current
+            let desc_changed = description.as_ref().is_some_and(|description| *description != todo.description);
+            let priority_changed = priority.as_ref().is_some_and(|priority| *priority != todo.priority);
+            let due_date_changed = due_date.is_some() && due_date != todo.due_date;
+            let color_changed = color.is_some() && color != todo.color;
+            if !(title_changed || desc_changed || priority_changed || due_date_changed || color_changed) {
+                return Ok(());
+            }
+            if title_changed {
+                self.release_title(&todo);
+            }
+            if let Some(title) = title {
+                todo.title = title;
+            }
+            if let Some(description) = description {
+                todo.description = description;
+            }
+            if let Some(priority) = priority {
+                todo.priority = priority;
+            }
+            if due_date.is_some() {
+                todo.due_date = due_date;
+            }
+            if color.is_some() {
+                todo.color = color;
+            }
+            todo.updated_at = self.env().block_timestamp();
+            self.save_todo(&mut todo);
+            if title_changed {
+                self.register_title(&todo);
+            }
+
+            // Emit event
+            self.env().emit_event(TodoEdited {
+                todo_id,
+                title_changed,
+                desc_changed,
+            });
+
+            Ok(())
+        }
+
+        /// Change the priority of a todo
+        #[ink(message)]
+        pub fn set_priority(&mut self, todo_id: u32, priority: Priority) -> Result<()> {
+            self.ensure_not_paused()?;
+
+            // Check if todo exists and belongs to the caller
+            let mut todo = self.owned_todo(todo_id)?;
+
+            // Update the todo
+            todo.priority = priority;
+            todo.updated_at = self.env().block_timestamp();
+            self.save_todo(&mut todo);
+
+            Ok(())
+        }
+
+        /// Set the deadline of a todo
+        #[ink(message)]
+        pub fn set_due_date(&mut self, todo_id: u32, due_date: u64) -> Result<()> {
+            self.ensure_not_paused()?;
+
+            // Check if todo exists and belongs to the caller
+            let mut todo = self.owned_todo(todo_id)?;
+
+            // Update the todo
+            todo.due_date = Some(due_date);
+            todo.updated_at = self.env().block_timestamp();
+            self.save_todo(&mut todo);
+
+            Ok(())
+        }
+
+        /// Set or clear the time before which a todo cannot be completed.
+        /// Unlike `due_date` this is a release time, not a deadline.
+        #[ink(message)]
+        pub fn set_not_before(&mut self, todo_id: u32, not_before: Option<u64>) -> Result<()> {
+            self.ensure_not_paused()?;
+
+            // Check if todo exists and belongs to the caller
+            let mut todo = self.owned_todo(todo_id)?;
+
+            // Update the todo
+            todo.not_before = not_before;
+            todo.updated_at = self.env().block_timestamp();
+            self.save_todo(&mut todo);
+
+            Ok(())
+        }
+
+        /// Make a todo recur every `interval` milliseconds once completed, or stop recurring with `None`
+        #[ink(message)]
+        pub fn set_recurrence(&mut self, todo_id: u32, interval: Option<u64>) -> Result<()> {
+            self.ensure_not_paused()?;
+
+            // Check if todo exists and belongs to the caller
+            let mut todo = self.owned_todo(todo_id)?;
+
+            // Update the todo
+            todo.recurrence = interval;
+            todo.updated_at = self.env().block_timestamp();
+            self.save_todo(&mut todo);
+
+            Ok(())
+        }
+
+        /// Pin or unpin a todo and return whether it is now a favorite
+        #[ink(message)]
+        pub fn toggle_favorite(&mut self, todo_id: u32) -> Result<bool> {
+            self.ensure_not_paused()?;
+
+            // Check if todo exists and belongs to the caller
+            let mut todo = self.owned_todo(todo_id)?;
+
+            // Update the todo
+            todo.favorite = !todo.favorite;
+            todo.updated_at = self.env().block_timestamp();
+            self.save_todo(&mut todo);
+
+            // Emit event
+            self.env().emit_event(TodoFavorited {
+                todo_id,
+                favorite: todo.favorite,
+            });
+
+            Ok(todo.favorite)
+        }
+
+        /// Check whether an open todo has passed its deadline.
+        /// Completed, cancelled or archived todos and todos without a due date are never overdue.
+        #[ink(message)]
+        pub fn is_overdue(&self, todo_id: u32) -> Result<bool> {
+            let todo = self.load_todo(todo_id)
+                .ok_or(Error::TodoNotFound)?;
+
+            Ok(self.todo_is_overdue(&todo))
+        }
+
+        /// Add a tag to a todo; adding a tag that is already present is a no-op
+        #[ink(message)]
+        pub fn add_tag(&mut self, todo_id: u32, tag: String) -> Result<()> {
+            self.ensure_not_paused()?;
+
+            // Check if todo exists and belongs to the caller
+            let mut todo = self.owned_todo(todo_id)?;
+
+            // Validate input
+            Self::validate_tag(&tag)?;
+            if todo.tags.contains(&tag) {
+                return Ok(());
+            }
+            if todo.tags.len() >= MAX_TAGS as usize {
+                return Err(Error::TooManyTags);
+            }
+
+            // Update the todo
+            todo.tags.push(tag);
+            todo.updated_at = self.env().block_timestamp();
+            self.save_todo(&mut todo);
+
+            Ok(())
+        }
+
+        /// Block a todo until `depends_on` is completed; adding an existing dependency is a no-op.
+        /// Only direct cycles (A depends on B depends on A) are rejected.
+        #[ink(message)]
+        pub fn add_dependency(&mut self, todo_id: u32, depends_on: u32) -> Result<()> {
+            self.ensure_not_paused()?;
+
+            // Check if todo exists and belongs to the caller
+            let mut todo = self.owned_todo(todo_id)?;
+
+            // Validate input
+            if depends_on == todo_id {
+                return Err(Error::InvalidDependency);
+            }
+            let dependency = self.load_todo(depends_on)
+                .ok_or(Error::TodoNotFound)?;
+            if dependency.blocked_by.contains(&todo_id) {
+                return Err(Error::InvalidDependency);
+            }
+            if todo.blocked_by.contains(&depends_on) {
+                return Ok(());
+            }
+            if todo.blocked_by.len() >= MAX_DEPENDENCIES as usize {
+                return Err(Error::TooManyDependencies);
+            }
+
+            // Update the todo
+            todo.blocked_by.push(depends_on);
+            todo.updated_at = self.env().block_timestamp();
+            self.save_todo(&mut todo);
+
+            Ok(())
+        }
+
+        /// Remove a tag from a todo; removing a missing tag is a no-op
+        #[ink(message)]
+        pub fn remove_tag(&mut self, todo_id: u32, tag: String) -> Result<()> {
+            self.ensure_not_paused()?;
+
+            // Check if todo exists and belongs to the caller
+            let mut todo = self.owned_todo(todo_id)?;
+
+            if let Some(pos) = todo.tags.iter().position(|t| *t == tag) {
+                todo.tags.remove(pos);
+                todo.updated_at = self.env().block_timestamp();
+                self.save_todo(&mut todo);
+            }
+
+            Ok(())
+        }
+
+        /// Hand a todo over to another account, moving it between both accounts' counters
+        #[ink(message)]
+        pub fn reassign_todo(&mut self, todo_id: u32, new_owner: AccountId) -> Result<()> {
+            self.ensure_not_paused()?;
+
+            // Check if todo exists and belongs to the caller
+            let mut todo = self.owned_todo(todo_id)?;
+
+            if new_owner == todo.owner {
+                return Err(Error::SameOwner);
+            }
+            if new_owner == Self::zero_account() {
+                return Err(Error::ZeroAccount);
+            }
+            let new_owner_held = self.todos_per_account.get(new_owner).unwrap_or(0);
+            if new_owner_held >= self.max_todos_per_account {
+                return Err(Error::AccountLimitReached);
+            }
+            if todo.is_active() && self.title_taken(new_owner, &todo.title, todo_id) {
+                return Err(Error::DuplicateTitle);
+            }
+
+            // Move the todo between the per-account counters
+            let from = todo.owner;
+            let from_held = self.todos_per_account.get(from).unwrap_or(0);
+            self.todos_per_account.insert(from, &from_held.saturating_sub(1));
+            self.todos_per_account.insert(new_owner, &new_owner_held.saturating_add(1));
+            self.index_remove(from, todo_id);
+            self.index_add(new_owner, todo_id);
+
+            // Update the todo
+            self.release_title(&todo);
+            todo.owner = new_owner;
+            todo.updated_at = self.env().block_timestamp();
+            self.save_todo(&mut todo);
+            self.register_title(&todo);
+
+            // Emit event
+            self.env().emit_event(TodoReassigned {
+                todo_id,
+                from,
+                to: new_owner,
+            });
+
+            Ok(())
+        }
+
+        /// Add a checklist item to a todo and return its subtask ID
+        #[ink(message)]
+        pub fn add_subtask(&mut self, todo_id: u32, text: String) -> Result<u32> {
+            self.ensure_not_paused()?;
+
+            // Check if todo exists and belongs to the caller
+            let mut todo = self.owned_todo(todo_id)?;
+
+            // Validate input
+            if text.is_empty() {
+                return Err(Error::SubtaskEmpty);
+            }
+            let issued = self.subtask_counter.get(todo_id).unwrap_or(0);
+            if issued >= MAX_SUBTASKS {
+                return Err(Error::TooManySubtasks);
+            }
+
+            // Store the subtask
+            let subtask_id = issued.saturating_add(1);
+            self.subtasks.insert((todo_id, subtask_id), &SubTask {
+                id: subtask_id,
+                text,
+                done: false,
+            });
+            self.subtask_counter.insert(todo_id, &subtask_id);
+
+            // Update the todo
+            todo.updated_at = self.env().block_timestamp();
+            self.save_todo(&mut todo);
+
+            Ok(subtask_id)
+        }
+
+        /// Flip the done flag of a checklist item
+        #[ink(message)]
+        pub fn toggle_subtask(&mut self, todo_id: u32, subtask_id: u32) -> Result<()> {
+            self.ensure_not_paused()?;
+
+            // Check if todo exists and belongs to the caller
+            let mut todo = self.owned_todo(todo_id)?;
+
+            let mut subtask = self.subtasks.get((todo_id, subtask_id))
+                .ok_or(Error::SubtaskNotFound)?;
+            subtask.done = !subtask.done;
+            self.subtasks.insert((todo_id, subtask_id), &subtask);
+
+            // Update the todo
+            todo.updated_at = self.env().block_timestamp();
+            self.save_todo(&mut todo);
+
+            Ok(())
+        }
+
+        /// Remove a checklist item
+        #[ink(message)]
+        pub fn remove_subtask(&mut self, todo_id: u32, subtask_id: u32) -> Result<()> {
+            self.ensure_not_paused()?;
+
+            // Check if todo exists and belongs to the caller
+            let mut todo = self.owned_todo(todo_id)?;
+
+            if !self.subtasks.contains((todo_id, subtask_id)) {
+                return Err(Error::SubtaskNotFound);
+            }
+            self.subtasks.remove((todo_id, subtask_id));
+
+            // Update the todo
+            todo.updated_at = self.env().block_timestamp();
+            self.save_todo(&mut todo);
+
+            Ok(())
+        }
+
+        /// List the checklist items of a todo in ascending ID order.
+        /// Returns an empty list when the todo does not exist.
+        #[ink(message)]
+        pub fn list_subtasks(&self, todo_id: u32) -> Vec<SubTask> {
+            if !self.todos.contains(todo_id) {
+                return Vec::new();
+            }
+
+            let issued = self.subtask_counter.get(todo_id).unwrap_or(0);
+            (1..=issued)
+                .filter_map(|subtask_id| self.subtasks.get((todo_id, subtask_id)))
+                .collect()
+        }
+
+        /// Percentage of checklist items that are done.
+        /// Returns `None` when the todo does not exist or has no subtasks.
+        #[ink(message)]
+        pub fn subtask_progress(&self, todo_id: u32) -> Option<u8> {
+            let subtasks = self.list_subtasks(todo_id);
+            if subtasks.is_empty() {
+                return None;
+            }
+
+            let done = subtasks.iter().filter(|subtask| subtask.done).count();
+            Some((done * 100 / subtasks.len()) as u8)
+        }
+
+        /// Move a todo to a new manual sort position
+        #[ink(message)]
+        pub fn reorder_todo(&mut self, todo_id: u32, new_position: u32) -> Result<()> {
+            self.ensure_not_paused()?;
+
+            // Check if todo exists and belongs to the caller
+            let mut todo = self.owned_todo(todo_id)?;
+
+            // Update the todo
+            todo.position = new_position;
+            todo.updated_at = self.env().block_timestamp();
+            self.save_todo(&mut todo);
+
+            Ok(())
+        }
+
+        /// Append a comment to a todo. Any account may comment, not just the owner or assignee,
+        /// since the thread is capped at `MAX_COMMENTS` and comments cannot be edited or removed.
+        #[ink(message)]
+        pub fn add_comment(&mut self, todo_id: u32, text: String) -> Result<()> {
+            self.ensure_not_paused()?;
+
+            // Check if todo exists
+            if !self.todos.contains(todo_id) {
+                return Err(Error::TodoNotFound);
+            }
+
+            // Validate input
+            if text.is_empty() {
+                return Err(Error::CommentEmpty);
+            }
+            if text.len() > MAX_COMMENT_LEN as usize {
+                return Err(Error::CommentTooLong);
+            }
+            let mut comments = self.comments.get(todo_id).unwrap_or_default();
+            if comments.len() >= MAX_COMMENTS as usize {
+                return Err(Error::TooManyComments);
+            }
+
+            let author = self.env().caller();
+            comments.push(Comment {
+                author,
+                text,
+                timestamp: self.env().block_timestamp(),
+            });
+            self.comments.insert(todo_id, &comments);
+
+            // Emit event
+            self.env().emit_event(CommentAdded { todo_id, author });
+
+            Ok(())
+        }
+
+        /// Get the comments on a todo, oldest first
+        #[ink(message)]
+        pub fn get_comments(&self, todo_id: u32) -> Vec<Comment> {
+            self.comments.get(todo_id).unwrap_or_default()
+        }
+
+        /// Assign a todo to another account so it can work on the status
+        #[ink(message)]
+        pub fn assign_todo(&mut self, todo_id: u32, assignee: AccountId) -> Result<()> {
+            self.ensure_not_paused()?;
+
+            // Check if todo exists and belongs to the caller
+            let mut todo = self.owned_todo(todo_id)?;
+
+            // Update the todo
+            todo.assignee = Some(assignee);
+            todo.updated_at = self.env().block_timestamp();
+            self.save_todo(&mut todo);
+
+            // Emit event
+            self.env().emit_event(TodoAssigned { todo_id, assignee });
+
+            Ok(())
+        }
+
+        /// Archive a todo, hiding it from default listings without removing it from storage
+        #[ink(message)]
+        pub fn archive_todo(&mut self, todo_id: u32) -> Result<()> {
+            self.ensure_not_paused()?;
+
+            self.set_archived(todo_id, true)?;
+
+            // Emit event
+            self.env().emit_event(TodoArchived { todo_id });
+
+            Ok(())
+        }
+
+        /// Restore an archived todo
+        #[ink(message)]
+        pub fn restore_todo(&mut self, todo_id: u32) -> Result<()> {
+            self.ensure_not_paused()?;
+
+            self.set_archived(todo_id, false)?;
+
+            // Emit event
+            self.env().emit_event(TodoRestored { todo_id });
+
+            Ok(())
+        }
+
+        /// Permanently delete a todo; use `archive_todo` to keep it recoverable.
+        /// Allowed for the todo owner and the contract owner, never for editors.
+        #[ink(message)]
+        pub fn delete_todo(&mut self, todo_id: u32) -> Result<()> {
+            self.ensure_not_paused()?;
+
+            // Check if todo exists and the caller may delete it
+            let todo = self.load_todo(todo_id)
+                .ok_or(Error::TodoNotFound)?;
+            let caller = self.env().caller();
+            if caller != todo.owner && caller != self.owner {
+                return Err(Error::NotOwner);
+            }
+
+            self.remove_todo(todo)?;
+
+            Ok(())
+        }
+
+        /// Delete up to `MAX_BATCH_SIZE` of the caller's completed todos.
+        /// Returns how many were removed and whether more remain; call again until it reports none.
+        #[ink(message)]
+        pub fn clear_completed(&mut self) -> Result<(u32, bool)> {
+            self.ensure_not_paused()?;
+
+            // Collect first so removing entries doesn't disturb the index we are walking
+            let mut completed: Vec<Todo> = self.todos_of(self.env().caller())
+                .into_iter()
+                .filter_map(|id| self.load_todo(id))
+                .filter(|todo| todo.status == TodoStatus::Completed)
+                .take(MAX_BATCH_SIZE as usize + 1)
+                .collect();
+            let more = completed.len() > MAX_BATCH_SIZE as usize;
+            completed.truncate(MAX_BATCH_SIZE as usize);
+
+            let removed = completed.len() as u32;
+            for todo in completed {
+                self.remove_todo(todo)?;
+            }
+
+            Ok((removed, more))
+        }
+
+        /// Delete up to `MAX_BATCH_SIZE` of the caller's todos, oldest received first.
+        /// Returns how many were removed and whether any remain; call again until it reports none.
+        #[ink(message)]
+        pub fn clear_mine(&mut self) -> Result<(u32, bool)> {
+            self.ensure_not_paused()?;
+
+            // Collect first so removing entries doesn't disturb the index we are walking
+            let ids = self.todos_of(self.env().caller());
+            let more = ids.len() > MAX_BATCH_SIZE as usize;
+            let batch: Vec<Todo> = ids
+                .into_iter()
+                .take(MAX_BATCH_SIZE as usize)
+                .filter_map(|id| self.load_todo(id))
+                .collect();
+
+            let removed = batch.len() as u32;
+            for todo in batch {
+                self.remove_todo(todo)?;
+            }
+
+            Ok((removed, more))
+        }
+
+        /// Delete the caller's todos like `clear_mine`, but only when `confirm` is set so a
+        /// default-decoded call does nothing. At most `MAX_BATCH_SIZE` todos are removed per
+        /// call; returns how many were and resets the caller's counter once none remain.
+        #[ink(message)]
+        pub fn delete_all_my_todos(&mut self, confirm: bool) -> Result<u32> {
+            self.ensure_not_paused()?;
+
+            if !confirm {
+                return Ok(0);
+            }
+            let (removed, more) = self.clear_mine()?;
+            if !more {
+                self.todos_per_account.remove(self.env().caller());
+            }
+
+            Ok(removed)
+        }
+
+        /// Get the `n` most recently created live todos, newest first. `n` is capped at `MAX_LIST_LIMIT`.
+        #[ink(message)]
+        pub fn last_n(&self, n: u32) -> Vec<Todo> {
+            let last = (self.last_id != 0).then_some(self.last_id);
+
+            core::iter::successors(last, |&id| self.prev_live.get(id))
+                .take(n.min(MAX_LIST_LIMIT) as usize)
+                .filter_map(|id| self.load_todo(id))
+                .collect()
+        }
+
+        /// List the existing todos among IDs `start_id..start_id + limit` in ascending order,
+        /// with `limit` capped at `MAX_LIST_LIMIT`. Deleted IDs are skipped, so a page may come
+        /// back short; pass `start_id + limit` as the next `start_id`.
+        /// Archived todos are only included when `include_archived` is set.
+        #[ink(message)]
+        pub fn list_todos(&self, start_id: u32, limit: u32, include_archived: bool) -> Vec<Todo> {
+            self.scan(start_id, limit, |todo| include_archived || !todo.archived)
+        }
+
+        /// List the archived todos among IDs `start..start + limit` in ascending order
+        #[ink(message)]
+        pub fn list_archived(&self, start: u32, limit: u32) -> Vec<Todo> {
+            self.scan(start, limit, |todo| todo.archived)
+        }
+
+        /// List the todos with the given priority among IDs `start..start + limit`
+        #[ink(message)]
+        pub fn list_by_priority(&self, p: Priority, start: u32, limit: u32) -> Vec<Todo> {
+            self.scan(start, limit, |todo| todo.priority == p)
+        }
+
+        /// List the todos carrying the given tag among IDs `start..start + limit`
+        #[ink(message)]
+        pub fn list_by_tag(&self, tag: String, start: u32, limit: u32) -> Vec<Todo> {
+            self.scan(start, limit, |todo| todo.tags.contains(&tag))
+        }
+
+        /// List the todos among IDs `start..start + limit` whose title contains `needle`,
+        /// ignoring ASCII case. `limit` is capped at `MAX_LIST_LIMIT` and bounds the IDs
+        /// read rather than the matches, so page on with `start + limit`.
+        #[ink(message)]
+        pub fn search_title(&self, needle: String, start: u32, limit: u32) -> Vec<Todo> {
+            let needle = needle.to_ascii_lowercase();
+            self.scan(start, limit, |todo| todo.title.to_ascii_lowercase().contains(&needle))
+        }
+
+        /// Get up to `MAX_LIST_LIMIT` of the caller's todos whose title or description contains
+        /// `needle`, ignoring ASCII case. Needles shorter than `MIN_SEARCH_LEN` match nothing.
+        #[ink(message)]
+        pub fn search_my_todos(&self, needle: String) -> Vec<Todo> {
+            if needle.len() < MIN_SEARCH_LEN as usize {
+                return Vec::new();
+            }
+            let needle = needle.to_ascii_lowercase();
+
+            self.list_my_todos()
+                .into_iter()
+                .filter(|todo| {
+                    todo.title.to_ascii_lowercase().contains(&needle)
+                        || todo.description.to_ascii_lowercase().contains(&needle)
+                })
+                .take(MAX_LIST_LIMIT as usize)
+                .collect()
+        }
+
+        /// Get the todos with the given status among IDs `start..start + limit` in ascending order.
+        /// `limit` is capped at `MAX_LIST_LIMIT`; continue from `start + limit`.
+        #[ink(message)]
+        pub fn get_todos_by_status(&self, status: TodoStatus, start: u32, limit: u32) -> Vec<Todo> {
+            self.scan(start, limit, |todo| todo.status == status)
+        }
+
+        /// Get the todos carrying the given tag among IDs `start..start + limit` in ascending order
+        #[ink(message)]
+        pub fn get_todos_by_tag(&self, tag: String, start: u32, limit: u32) -> Vec<Todo> {
+            self.scan(start, limit, |todo| todo.tags.contains(&tag))
+        }
+
+        /// List all todos held by the caller
+        #[ink(message)]
+        pub fn list_my_todos(&self) -> Vec<Todo> {
+            self.todos_of(self.env().caller())
+                .into_iter()
+                .filter_map(|id| self.load_todo(id))
+                .collect()
+        }
+
+        /// List the todos assigned to the caller among IDs `start..start + limit` in ascending order
+        #[ink(message)]
+        pub fn list_assigned_to_me(&self, start: u32, limit: u32) -> Vec<Todo> {
+            let caller = self.env().caller();
+
+            self.scan(start, limit, |todo| todo.assignee == Some(caller))
+        }
+
+        /// Filter the caller's todos by every provided criterion, ignoring `None` ones.
+        /// Returns up to `MAX_LIST_LIMIT` matches in ascending ID order.
+        #[ink(message)]
+        pub fn query_todos(
+            &self,
+            status: Option<TodoStatus>,
+            tag: Option<String>,
+            priority: Option<Priority>,
+        ) -> Vec<Todo> {
+            let mut ids = self.todos_of(self.env().caller());
+            ids.sort_unstable();
+
+            ids.into_iter()
+                .filter_map(|id| self.load_todo(id))
+                .filter(|todo| status.as_ref().is_none_or(|status| todo.status == *status))
+                .filter(|todo| tag.as_ref().is_none_or(|tag| todo.tags.contains(tag)))
+                .filter(|todo| priority.as_ref().is_none_or(|priority| todo.priority == *priority))
+                .take(MAX_LIST_LIMIT as usize)
+                .collect()
+        }
+
+        /// List the todos among IDs `start..start + limit` matching every criterion of `filter`.
+        /// `limit` is capped at `MAX_LIST_LIMIT` and bounds the IDs read, so a selective filter
+        /// may return a short or empty page; continue from `start + limit`.
+        #[ink(message)]
+        pub fn query(&self, filter: TodoFilter, start: u32, limit: u32) -> Vec<Todo> {
+            self.scan(start, limit, |todo| {
+                filter.status.as_ref().is_none_or(|status| todo.status == *status)
+                    && filter.owner.is_none_or(|owner| todo.owner == owner)
+                    && filter.priority.as_ref().is_none_or(|priority| todo.priority == *priority)
+            })
+        }
+
+        /// List the caller's todos with favorites first, each group sorted by `position`;
+        /// todos sharing a position keep ID order
+        #[ink(message)]
+        pub fn list_my_todos_ordered(&self) -> Vec<Todo> {
+            let mut todos = self.list_my_todos();
+            todos.sort_by_key(|todo| (!todo.favorite, todo.position, todo.id));
+            todos
+        }
+
+        /// List the caller's open todos due between now and `within_ms` milliseconds from now,
+        /// soonest first. Todos without a due date are skipped; at most `MAX_LIST_LIMIT` are returned.
+        #[ink(message)]
+        pub fn due_soon(&self, within_ms: u64) -> Vec<Todo> {
+            let now = self.env().block_timestamp();
+            let until = now.saturating_add(within_ms);
+
+            let mut todos: Vec<Todo> = self.list_my_todos()
+                .into_iter()
+                .filter(|todo| todo.is_active())
+                .filter(|todo| todo.due_date.is_some_and(|due_date| due_date >= now && due_date <= until))
+                .collect();
+            todos.sort_by_key(|todo| (todo.due_date, todo.id));
+            todos.truncate(MAX_LIST_LIMIT as usize);
+            todos
+        }
+
+        /// List the caller's favorite todos in the order they were received
+        #[ink(message)]
+        pub fn list_favorites(&self) -> Vec<Todo> {
+            self.list_my_todos()
+                .into_iter()
+                .filter(|todo| todo.favorite)
+                .collect()
+        }
+
+        /// Export all of the caller's todos for backup or migration.
+        /// Reads every todo the caller holds, so gas grows with `max_todos_per_account`.
+        #[ink(message)]
+        pub fn export_todos(&self) -> Vec<Todo> {
+            self.list_my_todos()
+        }
+
+        /// Export up to `limit` live todos in ascending ID order for migrating to a new contract,
+        /// following the live list from the todo `after` like `all_ids`. `limit` is capped at
+        /// `MAX_LIST_LIMIT`; pass the last exported ID as the next `after`.
+        #[ink(message)]
+        pub fn export_all(&self, after: u32, limit: u32) -> Vec<Todo> {
+            self.live_ids_after(after)
+                .take(limit.min(MAX_LIST_LIMIT) as usize)
+                .filter_map(|id| self.load_todo(id))
+                .collect()
+        }
+
+        /// Re-create exported todos under the caller with fresh IDs, returning them in order.
+        /// Incoming IDs, owners and dependencies are ignored, since dependency IDs refer to the
+        /// source contract; at most `MAX_BATCH_SIZE` items per call and
+        /// the whole batch is rejected if any item is invalid. Stakes are not imported;
+        /// `create_fee` is due per item and any excess is held as stake like in `create_todos`.
+        #[ink(message, payable)]
+        pub fn import_todos(&mut self, items: Vec<Todo>) -> Result<Vec<u32>> {
+            self.ensure_not_paused()?;
+            self.ensure_allowed()?;
+            if items.is_empty() {
+                return Err(Error::EmptyBatch);
+            }
+            let count = items.len() as u32;
+            let excess = self.charge_fee(count)?;
+
+            if items.len() > MAX_BATCH_SIZE as usize {
+                return Err(Error::BatchTooLarge);
+            }
+
+            // Validate the whole batch before touching storage
+            for todo in &items {
+                Self::validate_title(&todo.title)?;
+                Self::validate_description(&todo.description)?;
+                Self::validate_color(todo.color)?;
+                Self::dedupe_tags(todo.tags.clone())?;
+            }
+            let caller = self.env().caller();
+            self.ensure_capacity(caller, items.len() as u32)?;
+
+            let mut ids = Vec::with_capacity(items.len());
+            for (index, mut todo) in items.into_iter().enumerate() {
+                todo.owner = caller;
+                todo.blocked_by = Vec::new();
+                todo.stake = Self::stake_share(excess, count, index);
+                ids.push(self.insert_todo(todo)?);
+            }
+
+            Ok(ids)
+        }
+
+        /// Get the ID the next created todo will receive. Another transaction landing first
+        /// takes that ID, so treat this as a prediction only.
+        #[ink(message)]
+        pub fn peek_next_id(&self) -> u32 {
+            self.next_id
+        }
+
+        /// Get the highest todo ID ever issued. `next_id` only moves forward, so this never
+        /// decreases and IDs referenced off-chain are never handed out again.
+        #[ink(message)]
+        pub fn id_nonce(&self) -> u32 {
+            self.next_id.saturating_sub(1)
+        }
+
+        /// Get up to `limit` live todo IDs in ascending order, following the live list from
+        /// the todo `after` (0 starts at the first one). `limit` is capped at `MAX_LIST_LIMIT`;
+        /// pass the last returned ID as the next `after`. Returns nothing if `after` is not live.
+        #[ink(message)]
+        pub fn all_ids(&self, after: u32, limit: u32) -> Vec<u32> {
+            self.live_ids_after(after)
+                .take(limit.min(MAX_LIST_LIMIT) as usize)
+                .collect()
+        }
+
+        /// Get the IDs of all todos held by an account
+        #[ink(message)]
+        pub fn todos_of(&self, who: AccountId) -> Vec<u32> {
+            self.todos_by_owner.get(who).unwrap_or_default()
+        }
+
+        /// Get the number of todos ever created and the number still open
+        #[ink(message)]
+        pub fn counts(&self) -> (u32, u32) {
+            (self.total_created, self.active_count)
+        }
+
+        /// Get the number of live todos in the given status
+        #[ink(message)]
+        pub fn count_by_status(&self, status: TodoStatus) -> u32 {
+            match status {
+                TodoStatus::Pending => self.pending_count,
+                TodoStatus::Completed => self.completed_count,
+                TodoStatus::Cancelled => self.cancelled_count,
+                TodoStatus::InProgress => self.in_progress_count,
+            }
+        }
+
+        /// Get the number of todos currently stored
+        #[ink(message)]
+        pub fn count_todos(&self) -> u32 {
+            self.todo_count
+        }
+
+        /// Summarise the caller's todos. Only the caller's own ID list is read,
+        /// so gas is bounded by `max_todos_per_account`.
+        #[ink(message)]
+        pub fn get_statistics(&self) -> TodoStats {
+            let mut stats = TodoStats::default();
+
+            for todo in self.list_my_todos() {
+                stats.total = stats.total.saturating_add(1);
+                let counter = match todo.status {
+                    TodoStatus::Pending => &mut stats.pending,
+                    TodoStatus::Completed => &mut stats.completed,
+                    TodoStatus::Cancelled => &mut stats.cancelled,
+                    TodoStatus::InProgress => &mut stats.in_progress,
+                };
+                *counter = counter.saturating_add(1);
+                if self.todo_is_overdue(&todo) {
+                    stats.overdue = stats.overdue.saturating_add(1);
+                }
+            }
+
+            stats
+        }
+
+        /// Pause or resume all mutating messages. Only callable by the contract owner.
+        #[ink(message)]
+        pub fn set_paused(&mut self, paused: bool) -> Result<()> {
+            self.ensure_owner()?;
+
+            self.paused = paused;
+
+            // Emit event
+            let account = self.env().caller();
+            if paused {
+                self.env().emit_event(Paused { account });
+            } else {
+                self.env().emit_event(Unpaused { account });
+            }
+
+            Ok(())
+        }
+
+        /// Disable all mutating messages. Only callable by the contract owner.
+        #[ink(message)]
+        pub fn pause(&mut self) -> Result<()> {
+            self.set_paused(true)
+        }
+
+        /// Re-enable mutating messages. Only callable by the contract owner.
+        #[ink(message)]
+        pub fn unpause(&mut self) -> Result<()> {
+            self.set_paused(false)
+        }
+
+        /// Start handing the contract owner role to another account, same as `propose_owner`.
+        /// Ownership only changes once `new_owner` calls `accept_ownership`.
+        #[ink(message)]
+        pub fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<()> {
+            self.propose_owner(new_owner)
+        }
+
+        /// Propose a new contract owner, who must call `accept_ownership` to take over.
+        /// Only callable by the contract owner.
+        #[ink(message)]
+        pub fn propose_owner(&mut self, new_owner: AccountId) -> Result<()> {
+            self.ensure_owner()?;
+
+            if new_owner == Self::zero_account() {
+                return Err(Error::ZeroAccount);
+            }
+
+            self.pending_owner = Some(new_owner);
+
+            // Emit event
+            self.env().emit_event(OwnershipTransferStarted {
+                previous: self.owner,
+                new: new_owner,
+            });
+
+            Ok(())
+        }
+
+        /// Take over the contract owner role after being proposed
+        #[ink(message)]
+        pub fn accept_ownership(&mut self) -> Result<()> {
+            let caller = self.env().caller();
+            if self.pending_owner != Some(caller) {
+                return Err(Error::NotPendingOwner);
+            }
+
+            self.set_owner(caller);
+
+            Ok(())
+        }
+
+        /// Withdraw a pending ownership proposal. Only callable by the contract owner.
+        #[ink(message)]
+        pub fn cancel_ownership_transfer(&mut self) -> Result<()> {
+            self.ensure_owner()?;
+
+            self.pending_owner = None;
+
+            Ok(())
+        }
+
+        /// Get the account proposed as the next contract owner, if any
+        #[ink(message)]
+        pub fn pending_owner(&self) -> Option<AccountId> {
+            self.pending_owner
+        }
+
+        /// Give up the contract owner role for good by handing it to the zero account
+        #[ink(message)]
+        pub fn renounce_ownership(&mut self) -> Result<()> {
+            self.ensure_owner()?;
+
+            self.set_owner(Self::zero_account());
+
+            Ok(())
+        }
+
+        /// Get the contract owner
+        #[ink(message)]
+        pub fn owner(&self) -> AccountId {
+            self.owner
+        }
+
+        /// Set how many todos a single account may hold, at most `MAX_TODOS_PER_ACCOUNT`.
+        /// Only callable by the contract owner.
+        #[ink(message)]
+        pub fn set_max_todos(&mut self, max_todos: u32) -> Result<()> {
+            self.ensure_owner()?;
+            if max_todos > MAX_TODOS_PER_ACCOUNT {
+                return Err(Error::LimitTooHigh);
+            }
+
+            self.max_todos_per_account = max_todos;
+
+            Ok(())
+        }
+
+        /// Set or clear the limit on open todos across all accounts. Only callable by the contract owner.
+        /// Archived, completed and cancelled todos do not count towards it.
+        #[ink(message)]
+        pub fn set_max_active(&mut self, max_active: Option<u32>) -> Result<()> {
+            self.ensure_owner()?;
+
+            self.max_active = max_active;
+
+            Ok(())
+        }
+
+        /// Get the limit on open todos across all accounts, if any
+        #[ink(message)]
+        pub fn max_active(&self) -> Option<u32> {
+            self.max_active
+        }
+
+        /// Set or clear the limit on live todos across all accounts. Only callable by the contract owner.
+        /// Lowering it below the current count only blocks new todos.
+        #[ink(message)]
+        pub fn set_max_total(&mut self, max_total: Option<u32>) -> Result<()> {
+            self.ensure_owner()?;
+
+            self.max_total = max_total;
+
+            Ok(())
+        }
+
+        /// Get the limit on live todos across all accounts, if any
+        #[ink(message)]
+        pub fn max_total(&self) -> Option<u32> {
+            self.max_total
+        }
+
+        /// Get the number of todos currently held by an account
+        #[ink(message)]
+        pub fn todo_count_of(&self, account: AccountId) -> u32 {
+            self.todos_per_account.get(account).unwrap_or(0)
+        }
+
+        /// Set or clear the contract notified about completions. Only callable by the contract owner.
+        #[ink(message)]
+        pub fn set_notifier(&mut self, notifier: Option<AccountId>) -> Result<()> {
+            self.ensure_owner()?;
+
+            self.notifier = notifier;
+
+            Ok(())
+        }
+
+        /// Get the contract notified about completions, if any
+        #[ink(message)]
+        pub fn notifier(&self) -> Option<AccountId> {
+            self.notifier
+        }
+
+        /// Turn the creation allowlist on or off. Only callable by the contract owner.
+        /// Accounts left off the list keep managing the todos they already hold.
+        #[ink(message)]
+        pub fn set_allowlist_enabled(&mut self, enabled: bool) -> Result<()> {
+            self.ensure_owner()?;
+
+            self.allowlist_enabled = enabled;
+
+            Ok(())
+        }
+
+        /// Allow an account to create todos while the allowlist is enabled. Only callable by the contract owner.
+        #[ink(message)]
+        pub fn add_to_allowlist(&mut self, account: AccountId) -> Result<()> {
+            self.ensure_owner()?;
+
+            self.allowlist.insert(account, &());
+
+            // Emit event
+            self.env().emit_event(AllowlistAdded { account });
+
+            Ok(())
+        }
+
+        /// Take an account off the creation allowlist. Only callable by the contract owner.
+        #[ink(message)]
+        pub fn remove_from_allowlist(&mut self, account: AccountId) -> Result<()> {
+            self.ensure_owner()?;
+
+            self.allowlist.remove(account);
+
+            // Emit event
+            self.env().emit_event(AllowlistRemoved { account });
+
+            Ok(())
+        }
+
+        /// Check whether an account may currently create todos
+        #[ink(message)]
+        pub fn is_allowed(&self, account: AccountId) -> bool {
+            !self.allowlist_enabled || self.allowlist.contains(account)
+        }
+
+        /// Let an account update the status of and edit any todo. Only callable by the contract owner.
+        #[ink(message)]
+        pub fn grant_editor(&mut self, who: AccountId) -> Result<()> {
+            self.ensure_owner()?;
+
+            self.editors.insert(who, &true);
+
+            Ok(())
+        }
+
+        /// Take the editor role away from an account. Only callable by the contract owner.
+        #[ink(message)]
+        pub fn revoke_editor(&mut self, who: AccountId) -> Result<()> {
+            self.ensure_owner()?;
+
+            self.editors.remove(who);
+
+            Ok(())
+        }
+
+        /// Check whether an account holds the editor role
+        #[ink(message)]
+        pub fn is_editor(&self, who: AccountId) -> bool {
+            self.editors.get(who).unwrap_or(false)
+        }
+
+        /// Set the fee charged per created todo. Only callable by the contract owner.
+        #[ink(message)]
+        pub fn set_create_fee(&mut self, fee: Balance) -> Result<()> {
+            self.ensure_owner()?;
+
+            self.create_fee = fee;
+
+            Ok(())
+        }
+
+        /// Get the fee charged per created todo
+        #[ink(message)]
+        pub fn create_fee(&self) -> Balance {
+            self.create_fee
+        }
+
+        /// Turn duplicate-title rejection on or off. Only callable by the contract owner.
+        /// Todos created while it was off are not tracked and never count as duplicates.
+        #[ink(message)]
+        pub fn set_dedupe(&mut self, dedupe: bool) -> Result<()> {
+            self.ensure_owner()?;
+
+            self.dedupe = dedupe;
+
+            Ok(())
+        }
+
+        /// Check whether duplicate-title rejection is on
+        #[ink(message)]
+        pub fn dedupe(&self) -> bool {
+            self.dedupe
+        }
+
+        /// Send collected fees and forfeited stakes to `to`. Only callable by the contract owner.
+        /// Stakes of live todos are never withdrawable.
+        #[ink(message)]
+        pub fn withdraw(&mut self, amount: Balance, to: AccountId) -> Result<()> {
+            self.ensure_owner()?;
+
+            let available = self.env().balance().saturating_sub(self.total_staked);
+            if amount > available {
+                return Err(Error::InsufficientBalance);
+            }
+
+            self.env().transfer(to, amount)
+                .map_err(|_| Error::TransferFailed)?;
+
+            // Emit event
+            self.env().emit_event(Withdrawn { to, amount });
+
+            Ok(())
+        }
+
+        /// Same as `withdraw` taking the recipient first, kept under the name integrators asked for
+        #[ink(message)]
+        pub fn withdraw_fees(&mut self, to: AccountId, amount: Balance) -> Result<()> {
+            self.withdraw(amount, to)
+        }
+
+        /// Get the sum of stakes held by live todos and the total amount forfeited so far.
+        /// Withdrawals do not lower the forfeited total.
+        #[ink(message)]
+        pub fn stakes(&self) -> (Balance, Balance) {
+            (self.total_staked, self.forfeited)
+        }
+
+        /// Replace the contract code while keeping its storage. Only callable by the contract owner.
+        /// The new code must honour the storage layout documented on `InkyTodo`.
+        #[ink(message)]
+        pub fn set_code(&mut self, code_hash: Hash) -> Result<()> {
+            self.ensure_owner()?;
+
+            self.env().set_code_hash(&code_hash)
+                .map_err(|_| Error::UpgradeFailed)?;
+
+            // Emit event
+            self.env().emit_event(CodeUpgraded { code_hash });
+
+            Ok(())
+        }
+
+        /// Rewrite a stored todo in the latest `StoredTodo` layout. Only callable by the contract owner.
+        /// Older entries already decode on every read; this only saves the conversion on later reads.
+        /// Todos already up to date are left untouched, which for now is every todo since `V1`
+        /// is the only layout.
+        #[ink(message)]
+        pub fn migrate_todo(&mut self, todo_id: u32) -> Result<()> {
+            self.ensure_owner()?;
+
+            let stored = self.todos.get(todo_id)
+                .ok_or(Error::TodoNotFound)?;
+            if !stored.is_latest() {
+                self.todos.insert(todo_id, &StoredTodo::V1(stored.into()));
+            }
+
+            Ok(())
+        }
+
+        /// Describe this deployment so clients can check they talk to a compatible instance
+        #[ink(message)]
+        pub fn contract_info(&self) -> ContractInfo {
+            ContractInfo {
+                name: String::from(CONTRACT_NAME),
+                version: String::from(CONTRACT_RELEASE),
+                owner: self.owner,
+                paused: self.paused,
+                total_todos: self.todo_count,
+            }
+        }
+
+        /// Get the message and event schema version of this contract
+        #[ink(message)]
+        pub fn version(&self) -> u32 {
+            CONTRACT_VERSION
+        }
+
+        /// Whether writes are currently disabled
+        #[ink(message)]
+        pub fn is_paused(&self) -> bool {
+            self.paused
+        }
+
+        /// A new pending todo owned by the caller with default settings, ready for `insert_todo`
+        fn draft_todo(&self, title: String, description: String) -> Todo {
+            Todo {
+                id: 0,
+                owner: self.env().caller(),
+                title,
+                description,
+                status: TodoStatus::Pending,
+                priority: Priority::Medium,
+                created_at: 0,
+                updated_at: 0,
+                due_date: None,
+                completed_at: None,
+                archived: false,
+                tags: Vec::new(),
+                position: 0,
+                recurrence: None,
+                assignee: None,
+                stake: 0,
+                rev: 0,
+                blocked_by: Vec::new(),
+                favorite: false,
+                color: None,
+                not_before: None,
+            }
+        }
+
+        /// Validate and store a drafted todo under the next free ID.
+        /// ID, timestamps, position and revision are assigned here; the status is kept as drafted.
+        fn insert_todo(&mut self, mut todo: Todo) -> Result<u32> {
+            // Never overwrite a stored or deleted todo, even if `next_id` was tampered with
+            let mut todo_id = self.next_id;
+            while self.todos.contains(todo_id) || self.tombstones.contains(todo_id) {
+                todo_id = todo_id.checked_add(1).ok_or(Error::IdSpaceExhausted)?;
+            }
+            let next_id = todo_id.checked_add(1).ok_or(Error::IdSpaceExhausted)?;
+            let now = self.env().block_timestamp();
+            
+            // Validate input
+            Self::validate_title(&todo.title)?;
+            Self::validate_description(&todo.description)?;
+            Self::validate_color(todo.color)?;
+            todo.tags = Self::dedupe_tags(todo.tags)?;
+
+            let owner = todo.owner;
+            let held = self.todos_per_account.get(owner).unwrap_or(0);
+            if held >= self.max_todos_per_account {
+                return Err(Error::AccountLimitReached);
+            }
+            if self.max_total.is_some_and(|max| self.todo_count >= max) {
+                return Err(Error::TooManyTodos);
+            }
+            if todo.is_active() && self.max_active.is_some_and(|max| self.active_count >= max) {
+                return Err(Error::ActiveLimitReached);
+            }
+            if todo.is_active() && self.title_taken(owner, &todo.title, todo_id) {
+                return Err(Error::DuplicateTitle);
+            }
+
+            todo.id = todo_id;
+            todo.created_at = now;
+            todo.updated_at = now;
+            todo.position = todo_id;
+            todo.rev = 0;
+
+            self.save_todo(&mut todo);
+            self.register_title(&todo);
+            self.link_id(todo_id);
+            self.todos_per_account.insert(owner, &held.saturating_add(1));
+            self.index_add(owner, todo_id);
+            self.next_id = next_id;
+            self.total_created = self.total_created.saturating_add(1);
+            self.total_staked = self.total_staked.saturating_add(todo.stake);
+            self.todo_count = self.todo_count.saturating_add(1);
+            self.sync_active_count(false, todo.is_active());
+            let status_count = self.status_count_mut(&todo.status);
+            *status_count = status_count.saturating_add(1);
+
+            // Emit event
+            self.env().emit_event(TodoCreated {
+                todo_id,
+                caller: self.env().caller(),
+                title: todo.title,
+            });
+
+            Ok(todo_id)
+        }
+
+        /// Move a todo to `new_status` if `is_valid_transition` allows it.
+        /// Re-applying the current status succeeds without writing or emitting anything.
+        fn apply_status(&mut self, todo: Todo, new_status: TodoStatus) -> Result<()> {
+            if todo.status == new_status {
+                return Ok(());
+            }
+            if !is_valid_transition(&todo.status, &new_status) {
+                return Err(Error::InvalidTransition);
+            }
+
+            self.write_status(todo, new_status)
+        }
+
+        /// Move a todo to `new_status` without validating the transition,
+        /// keeping counters in sync and emitting `TodoUpdated`.
+        /// Completion is refused while a dependency is incomplete or before `not_before`, and
+        /// completing a recurring todo also creates its next occurrence.
+        fn write_status(&mut self, mut todo: Todo, new_status: TodoStatus) -> Result<()> {
+            self.check_status(&todo, &new_status)?;
+
+            let was_active = todo.is_active();
+            let old_count = self.status_count_mut(&todo.status);
+            *old_count = old_count.saturating_sub(1);
+            let new_count = self.status_count_mut(&new_status);
+            *new_count = new_count.saturating_add(1);
+
+            // Update the todo
+            let now = self.env().block_timestamp();
+            todo.completed_at = match new_status {
+                TodoStatus::Completed => Some(now),
+                _ => None,
+            };
+            let stake = match new_status {
+                TodoStatus::Pending | TodoStatus::InProgress => 0,
+                _ => core::mem::take(&mut todo.stake),
+            };
+            let previous_status = core::mem::replace(&mut todo.status, new_status);
+            todo.updated_at = now;
+            self.save_todo(&mut todo);
+            self.sync_active_count(was_active, todo.is_active());
+            if !was_active {
+                self.register_title(&todo);
+            }
+
+            // Emit event
+            self.env().emit_event(TodoUpdated {
+                todo_id: todo.id,
+                caller: self.env().caller(),
+                previous_status,
+                new_status: todo.status.clone(),
+            });
+
+            // Settle the stake: refund on completion, give it up on cancellation
+            match todo.status {
+                TodoStatus::Completed => self.refund_stake(todo.owner, stake)?,
+                TodoStatus::Cancelled => {
+                    self.give_up_stake(todo.owner, stake)?;
+                    self.release_title(&todo);
+                }
+                TodoStatus::Pending | TodoStatus::InProgress => {}
+            }
+
+            if todo.status == TodoStatus::Completed {
+                self.notify_completed(todo.id, todo.owner);
+            }
+
+            // Regenerate recurring todos, leaving the completed one as a record
+            if let (TodoStatus::Completed, Some(interval)) = (&todo.status, todo.recurrence) {
+                let base = todo.due_date.unwrap_or(now);
+                let mut next = todo;
+                next.status = TodoStatus::Pending;
+                next.completed_at = None;
+                next.due_date = Some(base.saturating_add(interval));
+                self.insert_todo(next)?;
+            }
+
+            Ok(())
+        }
+
+        /// Checks `write_status` runs before its first write: a reopened title must still be free
+        /// under `dedupe`, completion gates must pass, and a recurring todo needs room for its
+        /// next occurrence
+        fn check_status(&self, todo: &Todo, new_status: &TodoStatus) -> Result<()> {
+            let reactivated = !todo.archived && !todo.status.is_active() && new_status.is_active();
+            if reactivated && self.title_taken(todo.owner, &todo.title, todo.id) {
+                return Err(Error::DuplicateTitle);
+            }
+            if *new_status != TodoStatus::Completed {
+                return Ok(());
+            }
+            if !self.dependencies_done(todo) {
+                return Err(Error::DependenciesIncomplete);
+            }
+            if todo.not_before.is_some_and(|not_before| self.env().block_timestamp() < not_before) {
+                return Err(Error::TooEarly);
+            }
+
+            if todo.recurrence.is_some() {
+                // The completed todo stays held but stops being active
+                let held = self.todos_per_account.get(todo.owner).unwrap_or(0);
+                if held >= self.max_todos_per_account {
+                    return Err(Error::AccountLimitReached);
+                }
+                if self.max_total.is_some_and(|max| self.todo_count >= max) {
+                    return Err(Error::TooManyTodos);
+                }
+                let active = self.active_count.saturating_sub(u32::from(todo.is_active()));
+                if self.max_active.is_some_and(|max| active >= max) {
+                    return Err(Error::ActiveLimitReached);
+                }
+                if self.next_id.checked_add(1).is_none() {
+                    return Err(Error::IdSpaceExhausted);
+                }
+            }
+
+            Ok(())
+        }
+
+        /// Make sure the creation fee for `count` todos was paid and return the excess
+        fn charge_fee(&self, count: u32) -> Result<Balance> {
+            let due = self.create_fee.saturating_mul(Balance::from(count));
+            let paid = self.env().transferred_value();
+
+            paid.checked_sub(due).ok_or(Error::InsufficientFee)
+        }
+
+        /// Stake held by item `index` of a batch of `count` todos paid with `excess` above the fees
+        fn stake_share(excess: Balance, count: u32, index: usize) -> Balance {
+            let count = Balance::from(count.max(1));
+            let share = excess / count;
+            if index == 0 {
+                share.saturating_add(excess % count)
+            } else {
+                share
+            }
+        }
+
+        /// Return a settled stake to the todo owner, only releasing it from
+        /// `total_staked` once the transfer went through
+        fn refund_stake(&mut self, owner: AccountId, stake: Balance) -> Result<()> {
+            if stake == 0 {
+                return Ok(());
+            }
+
+            self.env().transfer(owner, stake)
+                .map_err(|_| Error::TransferFailed)?;
+            self.total_staked = self.total_staked.saturating_sub(stake);
+
+            Ok(())
+        }
+
+        /// Move a settled stake into the forfeited pot
+        fn forfeit_stake(&mut self, stake: Balance) {
+            self.total_staked = self.total_staked.saturating_sub(stake);
+            self.forfeited = self.forfeited.saturating_add(stake);
+        }
+
+        /// Settle the stake of a todo cancelled or deleted before completion. Only the todo
+        /// owner can forfeit it; when the contract owner, an editor or a sweeper ends the
+        /// todo the stake goes back to its owner.
+        fn give_up_stake(&mut self, owner: AccountId, stake: Balance) -> Result<()> {
+            if self.env().caller() == owner {
+                self.forfeit_stake(stake);
+                return Ok(());
+            }
+
+            self.refund_stake(owner, stake)
+        }
+
+        /// Call `on_todo_completed(todo_id, owner)` on the notifier, if one is set.
+        /// This is best effort: a failing callee never rolls back the completion
+        /// and is only reported through `NotifyFailed`. Mutating messages fail with
+        /// `ReentrantCall` until the call returns.
+        fn notify_completed(&mut self, todo_id: u32, owner: AccountId) {
+            let Some(notifier) = self.notifier else {
+                return;
+            };
+
+            self.locked.set(&true);
+            // Cap the callee's gas so a notifier burning it all cannot starve the completion
+            let result = build_call::<Environment>()
+                .call(notifier)
+                .ref_time_limit(NOTIFY_REF_TIME_LIMIT)
+                .proof_size_limit(NOTIFY_PROOF_SIZE_LIMIT)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("on_todo_completed")))
+                        .push_arg(todo_id)
+                        .push_arg(owner),
+                )
+                .returns::<()>()
+                .try_invoke();
+            self.locked.set(&false);
+
+            if !matches!(result, Ok(Ok(()))) {
+                // Emit event
+                self.env().emit_event(NotifyFailed { todo_id, notifier });
+            }
+        }
+
+        /// Remove a todo from storage, keeping indexes and counters in sync.
+        /// The stake is settled first so a failed refund leaves the todo untouched.
+        fn remove_todo(&mut self, todo: Todo) -> Result<()> {
+            let todo_id = todo.id;
+            self.give_up_stake(todo.owner, todo.stake)?;
+
+            // Remove from storage, leaving a tombstone for auditing
+            self.todos.remove(todo_id);
+            self.release_title(&todo);
+            self.tombstones.insert(todo_id, &(todo.title.clone(), self.env().block_timestamp()));
+            self.comments.remove(todo_id);
+            let issued = self.subtask_counter.take(todo_id).unwrap_or(0);
+            for subtask_id in 1..=issued {
+                self.subtasks.remove((todo_id, subtask_id));
+            }
+            self.unlink_id(todo_id);
+            self.todo_count = self.todo_count.saturating_sub(1);
+            let held = self.todos_per_account.get(todo.owner).unwrap_or(0);
+            self.todos_per_account.insert(todo.owner, &held.saturating_sub(1));
+            self.index_remove(todo.owner, todo_id);
+            if todo.is_active() {
+                self.active_count = self.active_count.saturating_sub(1);
+            }
+            let status_count = self.status_count_mut(&todo.status);
+            *status_count = status_count.saturating_sub(1);
+
+            // Emit events
+            self.env().emit_event(TodoDeleted {
+                todo_id,
+                caller: self.env().caller(),
+                title: todo.title.clone(),
+            });
+            self.env().emit_event(TodoSnapshot {
+                todo_id,
+                todo,
+                deleted: true,
+            });
+
+            Ok(())
+        }
+
+        /// Record `todo_id` in the owner's index
+        fn index_add(&mut self, owner: AccountId, todo_id: u32) {
+            let mut ids = self.todos_by_owner.get(owner).unwrap_or_default();
+            ids.push(todo_id);
+            self.todos_by_owner.insert(owner, &ids);
+        }
+
+        /// Drop `todo_id` from the owner's index
+        fn index_remove(&mut self, owner: AccountId, todo_id: u32) {
+            let mut ids = self.todos_by_owner.get(owner).unwrap_or_default();
+            ids.retain(|&id| id != todo_id);
+            if ids.is_empty() {
+                self.todos_by_owner.remove(owner);
+            } else {
+                self.todos_by_owner.insert(owner, &ids);
+            }
+        }
+
+        /// Load a todo in the latest layout, converting entries written by older builds
+        fn load_todo(&self, todo_id: u32) -> Option<Todo> {
+            self.todos.get(todo_id).map(Todo::from)
+        }
+
+        /// Bump the revision of a todo, write it to storage in the latest layout and emit its snapshot
+        fn save_todo(&mut self, todo: &mut Todo) {
+            todo.rev = todo.rev.wrapping_add(1);
+            self.todos.insert(todo.id, &StoredTodo::V1(todo.clone()));
+            self.env().emit_event(TodoSnapshot {
+                todo_id: todo.id,
+                todo: todo.clone(),
+                deleted: false,
+            });
+        }
+
+        /// Flip the archived flag of a todo owned by the caller
+        fn set_archived(&mut self, todo_id: u32, archived: bool) -> Result<()> {
+            // Check if todo exists and belongs to the caller
+            let mut todo = self.owned_todo(todo_id)?;
+
+            if todo.archived == archived {
+                return Err(if archived { Error::AlreadyArchived } else { Error::NotArchived });
+            }
+            if !archived && todo.status.is_active() && self.title_taken(todo.owner, &todo.title, todo_id) {
+                return Err(Error::DuplicateTitle);
+            }
+
+            let was_active = todo.is_active();
+            todo.archived = archived;
+            todo.updated_at = self.env().block_timestamp();
+            self.save_todo(&mut todo);
+            self.sync_active_count(was_active, todo.is_active());
+            if !was_active {
+                self.register_title(&todo);
+            }
+
+            Ok(())
+        }
+
+        /// The running counter tracking todos in `status`
+        fn status_count_mut(&mut self, status: &TodoStatus) -> &mut u32 {
+            match status {
+                TodoStatus::Pending => &mut self.pending_count,
+                TodoStatus::Completed => &mut self.completed_count,
+                TodoStatus::Cancelled => &mut self.cancelled_count,
+                TodoStatus::InProgress => &mut self.in_progress_count,
+            }
+        }
+
+        /// Keep the open count in sync when a todo crosses the active boundary
+        fn sync_active_count(&mut self, was_active: bool, is_active: bool) {
+            match (was_active, is_active) {
+                (true, false) => self.active_count = self.active_count.saturating_sub(1),
+                (false, true) => self.active_count = self.active_count.saturating_add(1),
+                _ => {}
+            }
+        }
+
+        /// Check that a title is acceptable for storage
+        fn validate_title(title: &str) -> Result<()> {
+            if title.is_empty() {
+                return Err(Error::TitleEmpty);
+            }
+            if title.len() > MAX_TITLE_LEN as usize {
+                return Err(Error::TitleTooLong);
+            }
+
+            Ok(())
+        }
+
+        /// Check that a description is acceptable for storage
+        fn validate_description(description: &str) -> Result<()> {
+            if description.len() > MAX_DESCRIPTION_LEN as usize {
+                return Err(Error::DescriptionTooLong);
+            }
+
+            Ok(())
+        }
+
+        /// Check that a tag is acceptable for storage
+        fn validate_tag(tag: &str) -> Result<()> {
+            if tag.is_empty() {
+                return Err(Error::TagEmpty);
+            }
+            if tag.len() > MAX_TAG_LEN as usize {
+                return Err(Error::TagTooLong);
+            }
+
+            Ok(())
+        }
+
+        /// Check that a color fits in `0xRRGGBB`
+        fn validate_color(color: Option<u32>) -> Result<()> {
+            if color.is_some_and(|color| color > MAX_COLOR) {
+                return Err(Error::InvalidColor);
+            }
+
+            Ok(())
+        }
+
+        /// Walk the live todo IDs following `after` in ascending order; 0 walks from the first
+        /// one and an `after` that is not live yields nothing
+        fn live_ids_after(&self, after: u32) -> impl Iterator<Item = u32> + '_ {
+            let first = match after {
+                0 => (self.first_id != 0).then_some(self.first_id),
+                _ => self.next_live.get(after),
+            };
+
+            core::iter::successors(first, |&id| self.next_live.get(id))
+        }
+
+        /// Append a new todo ID to the live list; IDs only grow, so the list stays sorted
+        fn link_id(&mut self, todo_id: u32) {
+            if self.last_id == 0 {
+                self.first_id = todo_id;
+            } else {
+                self.next_live.insert(self.last_id, &todo_id);
+                self.prev_live.insert(todo_id, &self.last_id);
+            }
+            self.last_id = todo_id;
+        }
+
+        /// Unlink a deleted todo ID from the live list, joining its neighbours
+        fn unlink_id(&mut self, todo_id: u32) {
+            let prev = self.prev_live.take(todo_id);
+            let next = self.next_live.take(todo_id);
+
+            match (prev, next) {
+                (Some(prev), Some(next)) => {
+                    self.next_live.insert(prev, &next);
+                    self.prev_live.insert(next, &prev);
+                }
+                (Some(prev), None) => {
+                    self.next_live.remove(prev);
+                    self.last_id = prev;
+                }
+                (None, Some(next)) => {
+                    self.prev_live.remove(next);
+                    self.first_id = next;
+                }
+                (None, None) => {
+                    self.first_id = 0;
+                    self.last_id = 0;
+                }
+            }
+        }
+
+        /// Validate a list of tags, dropping duplicates while keeping their order
+        fn dedupe_tags(tags: Vec<String>) -> Result<Vec<String>> {
+            let mut unique: Vec<String> = Vec::new();
+            for tag in tags {
+                Self::validate_tag(&tag)?;
+                if !unique.contains(&tag) {
+                    unique.push(tag);
+                }
+            }
+            if unique.len() > MAX_TAGS as usize {
+                return Err(Error::TooManyTags);
+            }
+
+            Ok(unique)
+        }
+
+        /// Make sure `count` more todos fit the owner's limit, `max_total`, the active limit and the ID space
+        fn ensure_capacity(&self, owner: AccountId, count: u32) -> Result<()> {
+            let held = self.todos_per_account.get(owner).unwrap_or(0);
+            if held.saturating_add(count) > self.max_todos_per_account {
+                return Err(Error::AccountLimitReached);
+            }
+            if self.max_total.is_some_and(|max| self.todo_count.saturating_add(count) > max) {
+                return Err(Error::TooManyTodos);
+            }
+            if self.max_active.is_some_and(|max| self.active_count.saturating_add(count) > max) {
+                return Err(Error::ActiveLimitReached);
+            }
+            if self.next_id.checked_add(count).is_none() {
+                return Err(Error::IdSpaceExhausted);
+            }
+
+            Ok(())
+        }
+
+        /// Collect the existing todos matching `filter` among IDs `start..start + limit`,
+        /// with `limit` capped at `MAX_LIST_LIMIT` so every call reads a bounded window
+        fn scan(&self, start: u32, limit: u32, filter: impl Fn(&Todo) -> bool) -> Vec<Todo> {
+            let start = start.max(1);
+            let end = start.saturating_add(limit.min(MAX_LIST_LIMIT)).min(self.next_id);
+
+            (start..end)
+                .filter_map(|id| self.load_todo(id))
+                .filter(|todo| filter(todo))
+                .collect()
+        }
+
+        /// Hash of a todo title as used in `title_ids`
+        fn title_hash(&self, title: &str) -> Hash {
+            Hash::from(self.env().hash_bytes::<ink::env::hash::Blake2x256>(title.as_bytes()))
+        }
+
+        /// Whether `dedupe` is on and another active todo of `owner` already uses `title`,
+        /// meaning todo `todo_id` may not become active under that owner and title
+        fn title_taken(&self, owner: AccountId, title: &str, todo_id: u32) -> bool {
+            if !self.dedupe {
+                return false;
+            }
+            let key = (owner, self.title_hash(title));
+
+            self.title_ids.get(key).is_some_and(|id| {
+                id != todo_id
+                    && self.load_todo(id).is_some_and(|existing| {
+                        existing.is_active() && existing.owner == owner && existing.title == title
+                    })
+            })
+        }
+
+        /// Record an active todo in `title_ids` so later duplicates are caught
+        fn register_title(&mut self, todo: &Todo) {
+            if !self.dedupe || !todo.is_active() {
+                return;
+            }
+            let key = (todo.owner, self.title_hash(&todo.title));
+            self.title_ids.insert(key, &todo.id);
+        }
+
+        /// Free the `title_ids` entry of a todo that is cancelled, deleted, renamed or reassigned
+        fn release_title(&mut self, todo: &Todo) {
+            if !self.dedupe {
+                return;
+            }
+            let key = (todo.owner, self.title_hash(&todo.title));
+            if self.title_ids.get(key) == Some(todo.id) {
+                self.title_ids.remove(key);
+            }
+        }
+
+        /// Whether every todo blocking `todo` is completed; deleted ones no longer block
+        fn dependencies_done(&self, todo: &Todo) -> bool {
+            todo.blocked_by.iter().all(|&id| {
+                self.load_todo(id).is_none_or(|dependency| dependency.status == TodoStatus::Completed)
+            })
+        }
+
+        /// Whether a todo is still open past its due date
+        fn todo_is_overdue(&self, todo: &Todo) -> bool {
+            todo.is_active()
+                && todo.due_date.is_some_and(|due_date| self.env().block_timestamp() > due_date)
+        }
+
+        /// Replace the contract owner, drop any pending proposal and emit `OwnershipTransferred`
+        fn set_owner(&mut self, new_owner: AccountId) {
+            let previous = self.owner;
+            self.owner = new_owner;
+            self.pending_owner = None;
+
+            // Emit event
+            self.env().emit_event(OwnershipTransferred {
+                previous,
+                new: new_owner,
+            });
+        }
+
+        /// The all-zero account, used as a burn address
+        fn zero_account() -> AccountId {
+            AccountId::from([0u8; 32])
+        }
+
+        /// Make sure the caller is the contract owner
+        fn ensure_owner(&self) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotContractOwner);
+            }
+
+            Ok(())
+        }
+
+        /// Reject writes while the contract is paused or the notifier is calling back into it
+        fn ensure_not_paused(&self) -> Result<()> {
+            if self.paused {
+                return Err(Error::ContractPaused);
+            }
+            if self.locked.get().unwrap_or(false) {
+                return Err(Error::ReentrantCall);
+            }
+
+            Ok(())
+        }
+
+        /// Reject todo creation by callers missing from an enabled allowlist
+        fn ensure_allowed(&self) -> Result<()> {
+            if !self.is_allowed(self.env().caller()) {
+                return Err(Error::NotAllowed);
+            }
+
+            Ok(())
+        }
+
+        /// Load a todo and make sure the caller is its owner, its assignee or an editor
+        fn workable_todo(&self, todo_id: u32) -> Result<Todo> {
+            let todo = self.load_todo(todo_id)
+                .ok_or(Error::TodoNotFound)?;
+
+            let caller = self.env().caller();
+            if caller != todo.owner && todo.assignee != Some(caller) && !self.is_editor(caller) {
+                return Err(Error::NotAssignee);
+            }
+
+            Ok(todo)
+        }
+
+        /// Load a todo and make sure the caller is its owner or an editor
+        fn editable_todo(&self, todo_id: u32) -> Result<Todo> {
+            let todo = self.load_todo(todo_id)
+                .ok_or(Error::TodoNotFound)?;
+
+            let caller = self.env().caller();
+            if caller != todo.owner && !self.is_editor(caller) {
+                return Err(Error::NotOwner);
+            }
+
+            Ok(todo)
+        }
+
+        /// Load a todo and make sure the caller owns it
+        fn owned_todo(&self, todo_id: u32) -> Result<Todo> {
+            let todo = self.load_todo(todo_id)
+                .ok_or(Error::TodoNotFound)?;
+
+            if self.env().caller() != todo.owner {
+                return Err(Error::NotOwner);
+            }
+
+            Ok(todo)
+        }
+
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        type Env = ink::env::DefaultEnvironment;
+
+        fn accounts() -> ink::env::test::DefaultAccounts<Env> {
+            ink::env::test::default_accounts::<Env>()
+        }
+
+        fn set_caller(caller: AccountId) {
+            ink::env::test::set_caller::<Env>(caller);
+        }
+
+        /// Create a plain todo as the current caller and return its ID
+        fn create(contract: &mut InkyTodo, title: &str) -> u32 {
+            contract.create_todo(title.into(), String::new(), None, Vec::new(), None).unwrap()
+        }
+
+        #[ink::test]
+        fn only_the_creator_can_delete_a_todo() {
+            let accounts = accounts();
+            let mut contract = InkyTodo::new();
+
+            set_caller(accounts.alice);
+            let todo_id = create(&mut contract, "Alice's todo");
+            assert_eq!(contract.get_todo(todo_id).unwrap().owner, accounts.alice);
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.delete_todo(todo_id), Err(Error::NotOwner));
+            assert!(contract.exists(todo_id));
+
+            set_caller(accounts.alice);
+            assert_eq!(contract.delete_todo(todo_id), Ok(()));
+            assert!(!contract.exists(todo_id));
+        }
+
+        #[ink::test]
+        fn list_todos_returns_at_most_limit_items() {
+            let mut contract = InkyTodo::new();
+            for title in ["one", "two", "three", "four", "five"] {
+                create(&mut contract, title);
+            }
+
+            let page = contract.list_todos(1, 3, false);
+            assert_eq!(page.len(), 3);
+            assert_eq!(page.iter().map(|todo| todo.id).collect::<Vec<_>>(), vec![1, 2, 3]);
+
+            let rest = contract.list_todos(4, 3, false);
+            assert_eq!(rest.iter().map(|todo| todo.id).collect::<Vec<_>>(), vec![4, 5]);
+        }
+
+        #[ink::test]
+        fn counts_follow_create_complete_and_delete() {
+            let mut contract = InkyTodo::new();
+            assert_eq!(contract.counts(), (0, 0));
+
+            let first = create(&mut contract, "first");
+            let second = create(&mut contract, "second");
+            create(&mut contract, "third");
+            assert_eq!(contract.counts(), (3, 3));
+
+            contract.complete_todo(first).unwrap();
+            assert_eq!(contract.counts(), (3, 2));
+
+            contract.delete_todo(second).unwrap();
+            assert_eq!(contract.counts(), (3, 1));
+
+            // Deleting a completed todo leaves the open count alone
+            contract.delete_todo(first).unwrap();
+            assert_eq!(contract.counts(), (3, 1));
+        }
+
+        fn set_timestamp(timestamp: u64) {
+            ink::env::test::set_block_timestamp::<Env>(timestamp);
+        }
+
+        #[ink::test]
+        fn status_update_moves_updated_at_only() {
+            let mut contract = InkyTodo::new();
+            set_timestamp(1_000);
+            let todo_id = create(&mut contract, "stamped");
+
+            set_timestamp(5_000);
+            contract.update_todo_status(todo_id, TodoStatus::InProgress).unwrap();
+
+            let todo = contract.get_todo(todo_id).unwrap();
+            assert_eq!(todo.created_at, 1_000);
+            assert_eq!(todo.updated_at, 5_000);
+        }
+
+        #[ink::test]
+        fn edit_without_title_keeps_the_title() {
+            let mut contract = InkyTodo::new();
+            let todo_id = contract
+                .create_todo("keep me".into(), "old".into(), None, Vec::new(), None)
+                .unwrap();
+
+            contract.edit_todo(todo_id, None, Some("new".into()), None, None, None).unwrap();
+
+            let todo = contract.get_todo(todo_id).unwrap();
+            assert_eq!(todo.title, "keep me");
+            assert_eq!(todo.description, "new");
+        }
+
+        #[ink::test]
+        fn list_by_priority_returns_only_matches() {
+            let mut contract = InkyTodo::new();
+            let low = contract.create_todo_with_priority("low".into(), String::new(), Priority::Low).unwrap();
+            let high = contract.create_todo_with_priority("high".into(), String::new(), Priority::High).unwrap();
+            let medium = create(&mut contract, "medium");
+            let also_high = contract.create_todo_with_priority("also high".into(), String::new(), Priority::High).unwrap();
+
+            let ids = |todos: Vec<Todo>| todos.into_iter().map(|todo| todo.id).collect::<Vec<_>>();
+            assert_eq!(ids(contract.list_by_priority(Priority::High, 1, 10)), vec![high, also_high]);
+            assert_eq!(ids(contract.list_by_priority(Priority::Medium, 1, 10)), vec![medium]);
+            assert_eq!(ids(contract.list_by_priority(Priority::Low, 1, 10)), vec![low]);
+        }
+
+        #[ink::test]
+        fn todo_becomes_overdue_after_its_due_date() {
+            let mut contract = InkyTodo::new();
+            set_timestamp(1_000);
+            let todo_id = contract
+                .create_todo("deadline".into(), String::new(), Some(2_000), Vec::new(), None)
+                .unwrap();
+
+            set_timestamp(2_000);
+            assert_eq!(contract.is_overdue(todo_id), Ok(false));
+
+            set_timestamp(2_001);
+            assert_eq!(contract.is_overdue(todo_id), Ok(true));
+
+            // Closed todos are never overdue
+            contract.complete_todo(todo_id).unwrap();
+            assert_eq!(contract.is_overdue(todo_id), Ok(false));
+            assert_eq!(contract.is_overdue(99), Err(Error::TodoNotFound));
+        }
+
+        #[ink::test]
+        fn created_at_is_the_block_timestamp() {
+            let mut contract = InkyTodo::new();
+            set_timestamp(42_000);
+
+            let todo_id = create(&mut contract, "timestamped");
+
+            assert_eq!(contract.get_todo(todo_id).unwrap().created_at, 42_000);
+        }
+
+        fn ids_of(todos: Vec<Todo>) -> Vec<u32> {
+            todos.into_iter().map(|todo| todo.id).collect()
+        }
+
+        #[ink::test]
+        fn create_todos_stores_the_whole_batch() {
+            let mut contract = InkyTodo::new();
+
+            let ids = contract
+                .create_todos(vec![("a".into(), "first".into()), ("b".into(), String::new())])
+                .unwrap();
+
+            assert_eq!(ids, vec![1, 2]);
+            assert_eq!(ids_of(contract.list_todos(1, 10, false)), vec![1, 2]);
+            assert_eq!(contract.get_todo(1).unwrap().description, "first");
+        }
+
+        #[ink::test]
+        fn create_todos_rejects_a_batch_with_an_empty_title() {
+            let mut contract = InkyTodo::new();
+
+            let result = contract.create_todos(vec![("a".into(), String::new()), (String::new(), String::new())]);
+
+            assert_eq!(result, Err(Error::TitleEmpty));
+            assert_eq!(contract.count_todos(), 0);
+            assert_eq!(contract.peek_next_id(), 1);
+        }
+
+        #[ink::test]
+        fn completion_stamps_completed_at() {
+            let mut contract = InkyTodo::new();
+            set_timestamp(1_000);
+            let todo_id = create(&mut contract, "finish me");
+            assert_eq!(contract.get_todo(todo_id).unwrap().completed_at, None);
+
+            set_timestamp(7_500);
+            contract.complete_todo(todo_id).unwrap();
+
+            assert_eq!(contract.get_todo(todo_id).unwrap().completed_at, Some(7_500));
+        }
+
+        #[ink::test]
+        fn bulk_update_status_skips_invalid_ids() {
+            let accounts = accounts();
+            let mut contract = InkyTodo::new();
+            let first = create(&mut contract, "first");
+            let second = create(&mut contract, "second");
+            set_caller(accounts.bob);
+            let bobs = create(&mut contract, "bob's");
+
+            set_caller(accounts.alice);
+            let updated = contract.bulk_update_status(vec![first, 99, bobs, second], TodoStatus::Completed);
+
+            assert_eq!(updated, Ok(2));
+            assert_eq!(contract.get_todo(first).unwrap().status, TodoStatus::Completed);
+            assert_eq!(contract.get_todo(second).unwrap().status, TodoStatus::Completed);
+            assert_eq!(contract.get_todo(bobs).unwrap().status, TodoStatus::Pending);
+        }
+
+        #[ink::test]
+        fn count_todos_only_counts_live_items() {
+            let mut contract = InkyTodo::new();
+            for title in ["one", "two", "three", "four"] {
+                create(&mut contract, title);
+            }
+
+            contract.delete_todo(2).unwrap();
+
+            assert_eq!(contract.count_todos(), 3);
+            assert_eq!(contract.peek_next_id(), 5);
+            assert_eq!(ids_of(contract.list_todos(1, 10, false)), vec![1, 3, 4]);
+        }
+
+        #[ink::test]
+        fn archived_todos_are_hidden_until_restored() {
+            let mut contract = InkyTodo::new();
+            let kept = create(&mut contract, "kept");
+            let archived = create(&mut contract, "archived");
+
+            contract.archive_todo(archived).unwrap();
+            assert_eq!(ids_of(contract.list_todos(1, 10, false)), vec![kept]);
+            assert_eq!(ids_of(contract.list_todos(1, 10, true)), vec![kept, archived]);
+            assert_eq!(ids_of(contract.list_archived(1, 10)), vec![archived]);
+            assert_eq!(contract.archive_todo(archived), Err(Error::AlreadyArchived));
+
+            contract.restore_todo(archived).unwrap();
+            assert_eq!(ids_of(contract.list_todos(1, 10, false)), vec![kept, archived]);
+            assert_eq!(contract.restore_todo(archived), Err(Error::NotArchived));
+        }
+
+        /// Decode every recorded event of type `E`, oldest first
+        fn events_of<E: ink::env::Event + scale::Decode>() -> Vec<E> {
+            let signature = E::SIGNATURE_TOPIC.expect("contract events are not anonymous");
+
+            ink::env::test::recorded_events()
+                .filter(|event| event.topics.first().is_some_and(|topic| topic[..] == signature[..]))
+                .map(|event| E::decode(&mut &event.data[..]).expect("event should decode"))
+                .collect()
+        }
+
+        #[ink::test]
+        fn every_write_emits_a_decodable_snapshot() {
+            let mut contract = InkyTodo::new();
+            let todo_id = create(&mut contract, "snapshotted");
+            contract.complete_todo(todo_id).unwrap();
+            contract.delete_todo(todo_id).unwrap();
+
+            let snapshots = events_of::<TodoSnapshot>();
+            assert_eq!(snapshots.len(), 3);
+            assert!(snapshots.iter().all(|snapshot| snapshot.todo_id == todo_id));
+
+            let created = &snapshots[0];
+            assert_eq!(created.todo.title, "snapshotted");
+            assert_eq!(created.todo.status, TodoStatus::Pending);
+            assert!(!created.deleted);
+
+            let completed = &snapshots[1];
+            assert_eq!(completed.todo.status, TodoStatus::Completed);
+            assert_eq!(completed.todo.rev, created.todo.rev + 1);
+            assert!(!completed.deleted);
+
+            let deleted = &snapshots[2];
+            assert_eq!(deleted.todo, completed.todo);
+            assert!(deleted.deleted);
+        }
+
+        #[ink::test]
+        fn paused_contract_rejects_writes() {
+            let mut contract = InkyTodo::new();
+            let todo_id = create(&mut contract, "before pause");
+
+            contract.set_paused(true).unwrap();
+            assert!(contract.is_paused());
+            assert_eq!(
+                contract.create_todo("while paused".into(), String::new(), None, Vec::new(), None),
+                Err(Error::ContractPaused)
+            );
+            assert_eq!(contract.complete_todo(todo_id), Err(Error::ContractPaused));
+
+            contract.set_paused(false).unwrap();
+            assert_eq!(
+                contract.create_todo("after pause".into(), String::new(), None, Vec::new(), None),
+                Ok(2)
+            );
+        }
+
+        #[ink::test]
+        fn admin_messages_reject_non_owners() {
+            let accounts = accounts();
+            let mut contract = InkyTodo::new();
+            assert_eq!(contract.owner(), accounts.alice);
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.set_paused(true), Err(Error::NotContractOwner));
+            assert_eq!(contract.set_max_todos(1), Err(Error::NotContractOwner));
+            assert_eq!(contract.set_create_fee(1), Err(Error::NotContractOwner));
+            assert_eq!(contract.grant_editor(accounts.bob), Err(Error::NotContractOwner));
+            assert_eq!(contract.withdraw(0, accounts.bob), Err(Error::NotContractOwner));
+            assert!(!contract.is_paused());
+
+            set_caller(accounts.alice);
+            assert_eq!(contract.set_paused(true), Ok(()));
+        }
+
+        #[ink::test]
+        fn ownership_handoff_requires_the_owner() {
+            let accounts = accounts();
+            let mut contract = InkyTodo::new();
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.transfer_ownership(accounts.bob), Err(Error::NotContractOwner));
+            assert_eq!(contract.renounce_ownership(), Err(Error::NotContractOwner));
+
+            set_caller(accounts.alice);
+            contract.transfer_ownership(accounts.bob).unwrap();
+            set_caller(accounts.bob);
+            contract.accept_ownership().unwrap();
+            assert_eq!(contract.owner(), accounts.bob);
+
+            // The previous owner lost its rights
+            set_caller(accounts.alice);
+            assert_eq!(contract.set_paused(true), Err(Error::NotContractOwner));
+
+            set_caller(accounts.bob);
+            contract.renounce_ownership().unwrap();
+            assert_eq!(contract.owner(), AccountId::from([0u8; 32]));
+        }
+
+        #[ink::test]
+        fn tags_are_deduplicated_and_capped() {
+            let mut contract = InkyTodo::new();
+            let todo_id = contract
+                .create_todo("tagged".into(), String::new(), None, vec!["work".into(), "work".into()], None)
+                .unwrap();
+            assert_eq!(contract.get_todo(todo_id).unwrap().tags, vec![String::from("work")]);
+
+            contract.add_tag(todo_id, "work".into()).unwrap();
+            assert_eq!(contract.get_todo(todo_id).unwrap().tags.len(), 1);
+
+            for index in 1..MAX_TAGS {
+                contract.add_tag(todo_id, format!("tag{index}")).unwrap();
+            }
+            assert_eq!(contract.get_todo(todo_id).unwrap().tags.len(), MAX_TAGS as usize);
+            assert_eq!(contract.add_tag(todo_id, "one too many".into()), Err(Error::TooManyTags));
+
+            let too_many: Vec<String> = (0..=MAX_TAGS).map(|index| format!("tag{index}")).collect();
+            assert_eq!(
+                contract.create_todo("overtagged".into(), String::new(), None, too_many, None),
+                Err(Error::TooManyTags)
+            );
+        }
+
+        #[ink::test]
+        fn pause_blocks_creation_until_unpaused() {
+            let mut contract = InkyTodo::new();
+
+            contract.pause().unwrap();
+            assert_eq!(
+                contract.create_todo("blocked".into(), String::new(), None, Vec::new(), None),
+                Err(Error::ContractPaused)
+            );
+
+            contract.unpause().unwrap();
+            assert_eq!(create(&mut contract, "allowed"), 1);
+        }
+
+        #[ink::test]
+        fn account_limit_is_enforced_and_freed_by_delete() {
+            let mut contract = InkyTodo::new();
+            contract.set_max_todos(2).unwrap();
+            let first = create(&mut contract, "first");
+            create(&mut contract, "second");
+
+            assert_eq!(
+                contract.create_todo("third".into(), String::new(), None, Vec::new(), None),
+                Err(Error::AccountLimitReached)
+            );
+
+            contract.delete_todo(first).unwrap();
+            assert_eq!(contract.todo_count_of(accounts().alice), 1);
+            assert_eq!(create(&mut contract, "third"), 3);
+        }
+
+        #[ink::test]
+        fn create_fails_cleanly_once_ids_run_out() {
+            let mut contract = InkyTodo::new();
+            let existing = create(&mut contract, "existing");
+            let before = contract.get_todo(existing).unwrap();
+
+            contract.next_id = u32::MAX;
+            assert_eq!(
+                contract.create_todo("overflow".into(), String::new(), None, Vec::new(), None),
+                Err(Error::IdSpaceExhausted)
+            );
+            assert_eq!(contract.next_id, u32::MAX);
+            assert_eq!(contract.get_todo(existing), Some(before));
+            assert!(!contract.exists(u32::MAX));
+            assert_eq!(contract.count_todos(), 1);
+        }
+
+        #[ink::test]
+        fn last_id_can_be_used_before_exhaustion() {
+            let mut contract = InkyTodo::new();
+            contract.next_id = u32::MAX - 1;
+
+            assert_eq!(create(&mut contract, "last one"), u32::MAX - 1);
+            assert_eq!(
+                contract.create_todo("one more".into(), String::new(), None, Vec::new(), None),
+                Err(Error::IdSpaceExhausted)
+            );
+            assert_eq!(
+                contract.create_todos(vec![("batched".into(), String::new())]),
+                Err(Error::IdSpaceExhausted)
+            );
+            assert_eq!(contract.count_todos(), 1);
+        }
+
+        #[ink::test]
+        fn get_many_keeps_missing_ids_in_place() {
+            let mut contract = InkyTodo::new();
+            create(&mut contract, "one");
+            create(&mut contract, "two");
+
+            let todos = contract.get_many(vec![2, 7, 1, 0]);
+
+            assert_eq!(todos.len(), 4);
+            assert_eq!(todos[0].as_ref().map(|todo| todo.id), Some(2));
+            assert_eq!(todos[1], None);
+            assert_eq!(todos[2].as_ref().map(|todo| todo.id), Some(1));
+            assert_eq!(todos[3], None);
+        }
+
+        #[ink::test]
+        fn reassign_moves_the_todo_between_counters() {
+            let accounts = accounts();
+            let mut contract = InkyTodo::new();
+            let todo_id = create(&mut contract, "handed over");
+            create(&mut contract, "kept");
+
+            assert_eq!(contract.reassign_todo(todo_id, accounts.alice), Err(Error::SameOwner));
+
+            contract.reassign_todo(todo_id, accounts.bob).unwrap();
+            assert_eq!(contract.get_todo(todo_id).unwrap().owner, accounts.bob);
+            assert_eq!(contract.todo_count_of(accounts.alice), 1);
+            assert_eq!(contract.todo_count_of(accounts.bob), 1);
+            assert_eq!(contract.todos_of(accounts.alice), vec![2]);
+            assert_eq!(contract.todos_of(accounts.bob), vec![todo_id]);
+        }
+
+        #[ink::test]
+        fn status_counters_follow_transitions() {
+            let mut contract = InkyTodo::new();
+            let first = create(&mut contract, "first");
+            let second = create(&mut contract, "second");
+            let third = create(&mut contract, "third");
+            let counters = |contract: &InkyTodo| {
+                (
+                    contract.count_by_status(TodoStatus::Pending),
+                    contract.count_by_status(TodoStatus::Completed),
+                    contract.count_by_status(TodoStatus::Cancelled),
+                )
+            };
+            assert_eq!(counters(&contract), (3, 0, 0));
+
+            contract.complete_todo(first).unwrap();
+            contract.cancel_todo(second).unwrap();
+            assert_eq!(counters(&contract), (1, 1, 1));
+
+            contract.reopen_todo(second, "not done after all".into()).unwrap();
+            assert_eq!(counters(&contract), (2, 1, 0));
+
+            contract.delete_todo(first).unwrap();
+            contract.complete_todo(third).unwrap();
+            assert_eq!(counters(&contract), (1, 1, 0));
+        }
+
+        #[ink::test]
+        fn new_owner_takes_over_updates() {
+            let accounts = accounts();
+            let mut contract = InkyTodo::new();
+            let todo_id = create(&mut contract, "transferred");
+            contract.reassign_todo(todo_id, accounts.bob).unwrap();
+
+            assert_eq!(contract.set_priority(todo_id, Priority::High), Err(Error::NotOwner));
+            assert_eq!(contract.complete_todo(todo_id), Err(Error::NotAssignee));
+
+            set_caller(accounts.bob);
+            contract.set_priority(todo_id, Priority::High).unwrap();
+            contract.complete_todo(todo_id).unwrap();
+
+            let todo = contract.get_todo(todo_id).unwrap();
+            assert_eq!(todo.priority, Priority::High);
+            assert_eq!(todo.status, TodoStatus::Completed);
+        }
+
+        #[test]
+        fn transition_table_is_exhaustive() {
+            use TodoStatus::*;
+            let statuses = [Pending, InProgress, Completed, Cancelled];
+            let allowed = [
+                (Pending, InProgress),
+                (Pending, Completed),
+                (Pending, Cancelled),
+                (InProgress, Pending),
+                (InProgress, Completed),
+                (InProgress, Cancelled),
+            ];
+
+            for from in &statuses {
+                for to in &statuses {
+                    let expected = allowed.contains(&(from.clone(), to.clone()));
+                    assert_eq!(is_valid_transition(from, to), expected, "{from:?} -> {to:?}");
+                }
+            }
+        }
+
+        #[ink::test]
+        fn each_account_index_holds_only_its_todos() {
+            let accounts = accounts();
+            let mut contract = InkyTodo::new();
+            let alice_first = create(&mut contract, "alice one");
+            set_caller(accounts.bob);
+            let bob_first = create(&mut contract, "bob one");
+            set_caller(accounts.alice);
+            let alice_second = create(&mut contract, "alice two");
+
+            assert_eq!(contract.todos_of(accounts.alice), vec![alice_first, alice_second]);
+            assert_eq!(contract.todos_of(accounts.bob), vec![bob_first]);
+            assert_eq!(ids_of(contract.list_my_todos()), vec![alice_first, alice_second]);
+            set_caller(accounts.bob);
+            assert_eq!(ids_of(contract.list_my_todos()), vec![bob_first]);
+            assert!(contract.todos_of(accounts.charlie).is_empty());
+        }
+
+        #[ink::test]
+        fn title_and_description_lengths_are_bounded() {
+            let mut contract = InkyTodo::new();
+            let title = "t".repeat(MAX_TITLE_LEN as usize);
+            let description = "d".repeat(MAX_DESCRIPTION_LEN as usize);
+
+            assert_eq!(contract.create_todo(title.clone(), description.clone(), None, Vec::new(), None), Ok(1));
+            assert_eq!(
+                contract.create_todo(title + "t", String::new(), None, Vec::new(), None),
+                Err(Error::TitleTooLong)
+            );
+            assert_eq!(
+                contract.create_todo("long description".into(), description + "d", None, Vec::new(), None),
+                Err(Error::DescriptionTooLong)
+            );
+            assert_eq!(
+                contract.create_todo(String::new(), String::new(), None, Vec::new(), None),
+                Err(Error::TitleEmpty)
+            );
+        }
+
+        #[ink::test]
+        fn closed_todos_reject_every_status_change() {
+            let mut contract = InkyTodo::new();
+            let completed = create(&mut contract, "completed");
+            let cancelled = create(&mut contract, "cancelled");
+            contract.complete_todo(completed).unwrap();
+            contract.cancel_todo(cancelled).unwrap();
+
+            for status in [TodoStatus::Pending, TodoStatus::InProgress, TodoStatus::Cancelled] {
+                assert_eq!(contract.update_todo_status(completed, status), Err(Error::InvalidTransition));
+            }
+            for status in [TodoStatus::Pending, TodoStatus::InProgress, TodoStatus::Completed] {
+                assert_eq!(contract.update_todo_status(cancelled, status), Err(Error::InvalidTransition));
+            }
+        }
+
+        #[ink::test]
+        fn repeating_the_current_status_records_no_event() {
+            let mut contract = InkyTodo::new();
+            let todo_id = create(&mut contract, "unchanged");
+            let rev = contract.get_todo(todo_id).unwrap().rev;
+            let recorded = ink::env::test::recorded_events().count();
+
+            assert_eq!(contract.update_todo_status(todo_id, TodoStatus::Pending), Ok(()));
+
+            assert_eq!(ink::env::test::recorded_events().count(), recorded);
+            assert!(events_of::<TodoUpdated>().is_empty());
+            assert_eq!(contract.get_todo(todo_id).unwrap().rev, rev);
+        }
+
+        #[ink::test]
+        fn failure_paths_return_specific_errors() {
+            let accounts = accounts();
+            let mut contract = InkyTodo::new();
+            let todo_id = create(&mut contract, "mine");
+
+            assert_eq!(contract.complete_todo(99), Err(Error::TodoNotFound));
+            assert_eq!(contract.add_tag(todo_id, String::new()), Err(Error::TagEmpty));
+            assert_eq!(contract.add_tag(todo_id, "t".repeat(MAX_TAG_LEN as usize + 1)), Err(Error::TagTooLong));
+            assert_eq!(contract.add_subtask(todo_id, String::new()), Err(Error::SubtaskEmpty));
+            assert_eq!(contract.toggle_subtask(todo_id, 1), Err(Error::SubtaskNotFound));
+            assert_eq!(contract.add_comment(todo_id, String::new()), Err(Error::CommentEmpty));
+            assert_eq!(contract.propose_owner(AccountId::from([0u8; 32])), Err(Error::ZeroAccount));
+            assert_eq!(
+                contract.create_todos(vec![(String::from("x"), String::new()); MAX_BATCH_SIZE as usize + 1]),
+                Err(Error::BatchTooLarge)
+            );
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.set_priority(todo_id, Priority::Low), Err(Error::NotOwner));
+            assert_eq!(contract.cancel_todo(todo_id), Err(Error::NotAssignee));
+            assert_eq!(contract.accept_ownership(), Err(Error::NotPendingOwner));
+            assert_eq!(contract.set_dedupe(true), Err(Error::NotContractOwner));
+        }
+
+        #[ink::test]
+        fn complete_todo_emits_todo_updated() {
+            let accounts = accounts();
+            let mut contract = InkyTodo::new();
+            let todo_id = create(&mut contract, "complete me");
+
+            contract.complete_todo(todo_id).unwrap();
+
+            assert_eq!(contract.get_todo(todo_id).unwrap().status, TodoStatus::Completed);
+            let updates = events_of::<TodoUpdated>();
+            assert_eq!(updates.len(), 1);
+            assert_eq!(updates[0].todo_id, todo_id);
+            assert_eq!(updates[0].caller, accounts.alice);
+            assert_eq!(updates[0].new_status, TodoStatus::Completed);
+        }
+
+        #[ink::test]
+        fn completing_a_recurring_todo_schedules_the_next_one() {
+            let mut contract = InkyTodo::new();
+            set_timestamp(1_000);
+            let todo_id = contract
+                .create_todo("water plants".into(), String::new(), Some(10_000), Vec::new(), None)
+                .unwrap();
+            contract.set_recurrence(todo_id, Some(86_400_000)).unwrap();
+
+            contract.complete_todo(todo_id).unwrap();
+
+            assert_eq!(contract.get_todo(todo_id).unwrap().status, TodoStatus::Completed);
+            let next = contract.get_todo(todo_id + 1).unwrap();
+            assert_eq!(next.title, "water plants");
+            assert_eq!(next.status, TodoStatus::Pending);
+            assert_eq!(next.due_date, Some(10_000 + 86_400_000));
+            assert_eq!(next.recurrence, Some(86_400_000));
+        }
+
+        #[ink::test]
+        fn recurring_completion_without_room_changes_nothing() {
+            let mut contract = InkyTodo::new();
+            contract.set_max_todos(1).unwrap();
+            let todo_id = create(&mut contract, "recurring");
+            contract.set_recurrence(todo_id, Some(1_000)).unwrap();
+            let before = contract.get_todo(todo_id).unwrap();
+
+            assert_eq!(contract.complete_todo(todo_id), Err(Error::AccountLimitReached));
+
+            assert_eq!(contract.get_todo(todo_id), Some(before));
+            assert_eq!(contract.count_by_status(TodoStatus::Completed), 0);
+            assert_eq!(contract.counts(), (1, 1));
+        }
+
+        #[ink::test]
+        fn deleted_todos_leave_a_tombstone() {
+            let mut contract = InkyTodo::new();
+            let todo_id = create(&mut contract, "gone soon");
+            assert_eq!(contract.deleted_info(todo_id), None);
+
+            set_timestamp(3_000);
+            contract.delete_todo(todo_id).unwrap();
+
+            assert_eq!(contract.deleted_info(todo_id), Some((String::from("gone soon"), 3_000)));
+            assert_eq!(contract.get_todo(todo_id), None);
+        }
+
+        #[ink::test]
+        fn subtasks_can_be_added_toggled_and_listed() {
+            let mut contract = InkyTodo::new();
+            let todo_id = create(&mut contract, "with checklist");
+
+            assert_eq!(contract.add_subtask(todo_id, "milk".into()), Ok(1));
+            assert_eq!(contract.add_subtask(todo_id, "eggs".into()), Ok(2));
+            contract.toggle_subtask(todo_id, 2).unwrap();
+
+            let subtasks = contract.list_subtasks(todo_id);
+            assert_eq!(subtasks.len(), 2);
+            assert_eq!((subtasks[0].id, subtasks[0].done), (1, false));
+            assert_eq!((subtasks[1].id, subtasks[1].done), (2, true));
+            assert_eq!(contract.subtask_progress(todo_id), Some(50));
+
+            contract.remove_subtask(todo_id, 1).unwrap();
+            assert_eq!(contract.list_subtasks(todo_id).len(), 1);
+        }
+
+        #[ink::test]
+        fn subtasks_of_a_deleted_todo_are_gone() {
+            let mut contract = InkyTodo::new();
+            let todo_id = create(&mut contract, "with checklist");
+            contract.add_subtask(todo_id, "milk".into()).unwrap();
+
+            contract.delete_todo(todo_id).unwrap();
+
+            assert!(contract.list_subtasks(todo_id).is_empty());
+            assert_eq!(contract.subtask_progress(todo_id), None);
+            assert_eq!(contract.toggle_subtask(todo_id, 1), Err(Error::TodoNotFound));
+            assert!(!contract.subtasks.contains((todo_id, 1)));
+        }
+
+        #[ink::test]
+        fn comments_keep_their_authors() {
+            let accounts = accounts();
+            let mut contract = InkyTodo::new();
+            let todo_id = create(&mut contract, "discussed");
+
+            contract.add_comment(todo_id, "from alice".into()).unwrap();
+            set_caller(accounts.bob);
+            contract.add_comment(todo_id, "from bob".into()).unwrap();
+
+            let comments = contract.get_comments(todo_id);
+            assert_eq!(comments.len(), 2);
+            assert_eq!((comments[0].author, comments[0].text.as_str()), (accounts.alice, "from alice"));
+            assert_eq!((comments[1].author, comments[1].text.as_str()), (accounts.bob, "from bob"));
+        }
+
+        #[ink::test]
+        fn export_all_returns_only_survivors() {
+            let mut contract = InkyTodo::new();
+            for title in ["one", "two", "three"] {
+                create(&mut contract, title);
+            }
+            set_caller(accounts().bob);
+            create(&mut contract, "four");
+            contract.delete_todo(4).unwrap();
+            set_caller(accounts().alice);
+            contract.delete_todo(2).unwrap();
+
+            let exported = contract.export_all(0, 10);
+
+            assert_eq!(ids_of(exported.clone()), vec![1, 3]);
+            assert_eq!(exported[1].title, "three");
+        }
+
+        /// Give the contract its own account, so transfers to the test accounts show up in their balances
+        fn use_contract_account() -> AccountId {
+            let contract = AccountId::from([0x42; 32]);
+            ink::env::test::set_callee::<Env>(contract);
+            ink::env::test::set_account_balance::<Env>(contract, 0);
+            contract
+        }
+
+        fn balance_of(account: AccountId) -> Balance {
+            ink::env::test::get_account_balance::<Env>(account).unwrap()
+        }
+
+        /// Run `call` with `value` moved from the caller to the contract
+        fn paying<T>(value: Balance, call: impl FnOnce() -> T) -> T {
+            ink::env::test::transfer_in::<Env>(value);
+            let result = call();
+            ink::env::test::set_value_transferred::<Env>(0);
+            result
+        }
+
+        #[ink::test]
+        fn completion_refunds_the_stake() {
+            let accounts = accounts();
+            let contract_account = use_contract_account();
+            let mut contract = InkyTodo::new();
+            let alice_before = balance_of(accounts.alice);
+
+            let todo_id = paying(100, || {
+                contract.create_todo("staked".into(), String::new(), None, Vec::new(), None)
+            })
+            .unwrap();
+            assert_eq!(contract.get_todo(todo_id).unwrap().stake, 100);
+            assert_eq!(contract.stakes(), (100, 0));
+            assert_eq!(balance_of(contract_account), 100);
+
+            contract.complete_todo(todo_id).unwrap();
+
+            assert_eq!(contract.get_todo(todo_id).unwrap().stake, 0);
+            assert_eq!(contract.stakes(), (0, 0));
+            assert_eq!(balance_of(contract_account), 0);
+            assert_eq!(balance_of(accounts.alice), alice_before);
+        }
+
+        #[ink::test]
+        fn cancellation_forfeits_the_stake() {
+            let accounts = accounts();
+            let contract_account = use_contract_account();
+            let mut contract = InkyTodo::new();
+            let alice_before = balance_of(accounts.alice);
+
+            let todo_id = paying(50, || {
+                contract.create_todo("staked".into(), String::new(), None, Vec::new(), None)
+            })
+            .unwrap();
+            contract.cancel_todo(todo_id).unwrap();
+
+            assert_eq!(contract.stakes(), (0, 50));
+            assert_eq!(balance_of(contract_account), 50);
+            assert_eq!(balance_of(accounts.alice), alice_before - 50);
+        }
+
+        #[ink::test]
+        fn batch_overpayment_is_split_across_the_stakes() {
+            use_contract_account();
+            let mut contract = InkyTodo::new();
+
+            let ids = paying(101, || {
+                contract.create_todos(vec![("a".into(), String::new()), ("b".into(), String::new())])
+            })
+            .unwrap();
+
+            assert_eq!(contract.get_todo(ids[0]).unwrap().stake, 51);
+            assert_eq!(contract.get_todo(ids[1]).unwrap().stake, 50);
+            assert_eq!(contract.stakes(), (101, 0));
+
+            assert_eq!(contract.bulk_update_status(ids.clone(), TodoStatus::Completed), Ok(2));
+            assert_eq!(contract.stakes(), (0, 0));
+        }
+
+        #[ink::test]
+        fn recurring_todo_without_due_date_recurs_from_completion() {
+            let mut contract = InkyTodo::new();
+            let todo_id = create(&mut contract, "weekly review");
+            contract.set_recurrence(todo_id, Some(7_000)).unwrap();
+
+            set_timestamp(20_000);
+            contract.complete_todo(todo_id).unwrap();
+
+            assert_eq!(contract.count_todos(), 2);
+            let next = contract.get_todo(todo_id + 1).unwrap();
+            assert_eq!(next.status, TodoStatus::Pending);
+            assert_eq!(next.due_date, Some(27_000));
+            assert_eq!(next.completed_at, None);
+        }
+
+        #[ink::test]
+        fn creation_fee_must_be_paid() {
+            let mut contract = InkyTodo::new();
+            contract.set_create_fee(10).unwrap();
+
+            ink::env::test::set_value_transferred::<Env>(9);
+            assert_eq!(
+                contract.create_todo("underpaid".into(), String::new(), None, Vec::new(), None),
+                Err(Error::InsufficientFee)
+            );
+            assert_eq!(
+                contract.create_todos(vec![("a".into(), String::new()), ("b".into(), String::new())]),
+                Err(Error::InsufficientFee)
+            );
+
+            ink::env::test::set_value_transferred::<Env>(10);
+            let todo_id = create(&mut contract, "exact");
+            assert_eq!(contract.get_todo(todo_id).unwrap().stake, 0);
+        }
+
+        #[ink::test]
+        fn owner_withdraws_collected_fees() {
+            let accounts = accounts();
+            let contract_account = use_contract_account();
+            let mut contract = InkyTodo::new();
+            contract.set_create_fee(10).unwrap();
+            paying(10, || create(&mut contract, "first"));
+            paying(10, || create(&mut contract, "second"));
+            assert_eq!(balance_of(contract_account), 20);
+
+            let django_before = balance_of(accounts.django);
+            contract.withdraw_fees(accounts.django, 20).unwrap();
+
+            assert_eq!(balance_of(contract_account), 0);
+            assert_eq!(balance_of(accounts.django), django_before + 20);
+        }
+
+        #[ink::test]
+        fn query_todos_combines_present_filters() {
+            let mut contract = InkyTodo::new();
+            let work_high = contract.create_todo_with_priority("a".into(), String::new(), Priority::High).unwrap();
+            contract.add_tag(work_high, "work".into()).unwrap();
+            let work_low = contract.create_todo_with_priority("b".into(), String::new(), Priority::Low).unwrap();
+            contract.add_tag(work_low, "work".into()).unwrap();
+            contract.complete_todo(work_low).unwrap();
+            let home_high = contract.create_todo_with_priority("c".into(), String::new(), Priority::High).unwrap();
+            contract.add_tag(home_high, "home".into()).unwrap();
+            contract.complete_todo(home_high).unwrap();
+            let plain = create(&mut contract, "d");
+            set_caller(accounts().bob);
+            create(&mut contract, "not mine");
+            set_caller(accounts().alice);
+
+            let pending = || Some(TodoStatus::Pending);
+            let work = || Some(String::from("work"));
+            let high = || Some(Priority::High);
+            let query = |status, tag, priority| ids_of(contract.query_todos(status, tag, priority));
+            assert_eq!(query(None, None, None), vec![work_high, work_low, home_high, plain]);
+            assert_eq!(query(pending(), None, None), vec![work_high, plain]);
+            assert_eq!(query(None, work(), None), vec![work_high, work_low]);
+            assert_eq!(query(None, None, high()), vec![work_high, home_high]);
+            assert_eq!(query(pending(), work(), None), vec![work_high]);
+            assert_eq!(query(pending(), None, high()), vec![work_high]);
+            assert_eq!(query(None, work(), high()), vec![work_high]);
+            assert_eq!(query(pending(), work(), high()), vec![work_high]);
+            assert!(query(Some(TodoStatus::Completed), Some("home".into()), Some(Priority::Low)).is_empty());
+        }
+
+        #[ink::test]
+        fn withdraw_only_pays_out_unstaked_funds_to_the_owner() {
+            let accounts = accounts();
+            let contract_account = use_contract_account();
+            let mut contract = InkyTodo::new();
+            contract.set_create_fee(10).unwrap();
+            paying(40, || create(&mut contract, "staked"));
+            assert_eq!(contract.stakes(), (30, 0));
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.withdraw(10, accounts.bob), Err(Error::NotContractOwner));
+
+            set_caller(accounts.alice);
+            assert_eq!(contract.withdraw(11, accounts.django), Err(Error::InsufficientBalance));
+
+            contract.withdraw(10, accounts.django).unwrap();
+            assert_eq!(balance_of(accounts.django), 10);
+            assert_eq!(balance_of(contract_account), 30);
+            assert_eq!(contract.withdraw(1, accounts.django), Err(Error::InsufficientBalance));
+        }
+
+        #[ink::test]
+        fn sweep_cancels_only_overdue_todos() {
+            let mut contract = InkyTodo::new();
+            let overdue = contract
+                .create_todo("overdue".into(), String::new(), Some(1_000), Vec::new(), None)
+                .unwrap();
+            let not_yet_due = contract
+                .create_todo("later".into(), String::new(), Some(9_000), Vec::new(), None)
+                .unwrap();
+            let no_deadline = create(&mut contract, "whenever");
+
+            set_timestamp(5_000);
+            set_caller(accounts().bob);
+            assert_eq!(contract.sweep_overdue(1, 10), Ok(1));
+
+            assert_eq!(contract.get_todo(overdue).unwrap().status, TodoStatus::Cancelled);
+            assert_eq!(contract.get_todo(not_yet_due).unwrap().status, TodoStatus::Pending);
+            assert_eq!(contract.get_todo(no_deadline).unwrap().status, TodoStatus::Pending);
+            assert_eq!(contract.sweep_overdue(1, 10), Ok(0));
+        }
+
+        #[ink::test]
+        fn unrelated_account_cannot_accept_ownership() {
+            let accounts = accounts();
+            let mut contract = InkyTodo::new();
+            contract.propose_owner(accounts.bob).unwrap();
+
+            set_caller(accounts.charlie);
+            assert_eq!(contract.accept_ownership(), Err(Error::NotPendingOwner));
+            assert_eq!(contract.owner(), accounts.alice);
+
+            set_caller(accounts.bob);
+            contract.accept_ownership().unwrap();
+
+            let started = events_of::<OwnershipTransferStarted>();
+            assert_eq!(started.len(), 1);
+            assert_eq!((started[0].previous, started[0].new), (accounts.alice, accounts.bob));
+            let transferred = events_of::<OwnershipTransferred>();
+            assert_eq!(transferred.len(), 1);
+            assert_eq!((transferred[0].previous, transferred[0].new), (accounts.alice, accounts.bob));
+        }
+
+        // The off-chain environment cannot swap code, so only the access check of
+        // `set_code` is covered here; the swap itself needs an e2e test against a node.
+        #[ink::test]
+        fn set_code_rejects_non_owners() {
+            let mut contract = InkyTodo::new();
+
+            set_caller(accounts().bob);
+            assert_eq!(contract.set_code(Hash::from([0x11; 32])), Err(Error::NotContractOwner));
+            assert!(events_of::<CodeUpgraded>().is_empty());
+        }
+
+        #[ink::test]
+        fn editors_lose_access_when_revoked() {
+            let accounts = accounts();
+            let mut contract = InkyTodo::new();
+            set_caller(accounts.bob);
+            let todo_id = create(&mut contract, "bob's");
+
+            set_caller(accounts.alice);
+            contract.grant_editor(accounts.charlie).unwrap();
+            set_caller(accounts.charlie);
+            contract.update_todo_status(todo_id, TodoStatus::InProgress).unwrap();
+            contract.edit_todo(todo_id, Some("edited".into()), None, None, None, None).unwrap();
+            assert_eq!(contract.get_todo(todo_id).unwrap().title, "edited");
+            assert_eq!(contract.delete_todo(todo_id), Err(Error::NotOwner));
+
+            set_caller(accounts.alice);
+            contract.revoke_editor(accounts.charlie).unwrap();
+            assert!(!contract.is_editor(accounts.charlie));
+            set_caller(accounts.charlie);
+            assert_eq!(contract.update_todo_status(todo_id, TodoStatus::Completed), Err(Error::NotAssignee));
+            assert_eq!(
+                contract.edit_todo(todo_id, Some("again".into()), None, None, None, None),
+                Err(Error::NotOwner)
+            );
+        }
+
+        // Reaching `set_code_hash`, which the off-chain environment does not implement,
+        // shows the owner passes the access check
+        #[ink::test]
+        #[should_panic(expected = "does not support `set_code_hash`")]
+        fn set_code_lets_the_owner_swap_code() {
+            let mut contract = InkyTodo::new();
+
+            let _ = contract.set_code(Hash::from([0x11; 32]));
+        }
+
+        #[ink::test]
+        fn live_index_matches_storage_after_random_deletes() {
+            let mut contract = InkyTodo::new();
+            let mut live: Vec<u32> = Vec::new();
+            // Deterministic xorshift so failures are reproducible
+            let mut seed: u32 = 0x9E37_79B9;
+            let mut next_random = move || {
+                seed ^= seed << 13;
+                seed ^= seed >> 17;
+                seed ^= seed << 5;
+                seed
+            };
+
+            for round in 0..300 {
+                if live.is_empty() || next_random() % 3 != 0 {
+                    live.push(create(&mut contract, &format!("todo {round}")));
+                } else {
+                    let todo_id = live.remove(next_random() as usize % live.len());
+                    contract.delete_todo(todo_id).unwrap();
+                }
+            }
+
+            let mut paged = Vec::new();
+            let mut after = 0;
+            loop {
+                let page = contract.all_ids(after, 7);
+                let Some(&last) = page.last() else {
+                    break;
+                };
+                paged.extend(page);
+                after = last;
+            }
+            assert_eq!(paged, live);
+            assert_eq!(contract.live_ids_after(0).collect::<Vec<_>>(), live);
+            assert_eq!(ids_of(contract.export_all(0, 7)), live[..7.min(live.len())]);
+            let newest: Vec<u32> = live.iter().rev().take(5).copied().collect();
+            assert_eq!(ids_of(contract.last_n(5)), newest);
+            for todo_id in 1..contract.peek_next_id() {
+                assert_eq!(contract.exists(todo_id), live.contains(&todo_id), "todo {todo_id}");
+            }
+            assert_eq!(contract.count_todos(), live.len() as u32);
+        }
+
+        #[ink::test]
+        fn search_title_ignores_case() {
+            let mut contract = InkyTodo::new();
+            let milk = create(&mut contract, "buy milk");
+            create(&mut contract, "walk the dog");
+
+            assert_eq!(ids_of(contract.search_title("Buy".into(), 1, 10)), vec![milk]);
+            assert_eq!(ids_of(contract.search_title("MILK".into(), 1, 10)), vec![milk]);
+            assert!(contract.search_title("bread".into(), 1, 10).is_empty());
+        }
+
+        #[ink::test]
+        fn statistics_count_the_callers_todos() {
+            let mut contract = InkyTodo::new();
+            set_timestamp(1_000);
+            create(&mut contract, "pending");
+            let overdue = contract
+                .create_todo("overdue".into(), String::new(), Some(2_000), Vec::new(), None)
+                .unwrap();
+            let in_progress = create(&mut contract, "in progress");
+            contract.update_todo_status(in_progress, TodoStatus::InProgress).unwrap();
+            let completed = create(&mut contract, "completed");
+            contract.complete_todo(completed).unwrap();
+            let cancelled = create(&mut contract, "cancelled");
+            contract.cancel_todo(cancelled).unwrap();
+            set_caller(accounts().bob);
+            create(&mut contract, "not counted");
+            set_caller(accounts().alice);
+            set_timestamp(3_000);
+
+            assert_eq!(
+                contract.get_statistics(),
+                TodoStats {
+                    total: 5,
+                    pending: 2,
+                    completed: 1,
+                    cancelled: 1,
+                    overdue: 1,
+                    in_progress: 1,
+                }
+            );
+            assert!(contract.is_overdue(overdue).unwrap());
+        }
+
+        #[ink::test]
+        fn duplicate_titles_depend_on_dedupe() {
+            let accounts = accounts();
+            let mut contract = InkyTodo::new();
+            create(&mut contract, "same");
+            assert_eq!(create(&mut contract, "same"), 2);
+
+            contract.set_dedupe(true).unwrap();
+            create(&mut contract, "tracked");
+            assert_eq!(
+                contract.create_todo("tracked".into(), String::new(), None, Vec::new(), None),
+                Err(Error::DuplicateTitle)
+            );
+
+            // Other owners and closed todos don't count as duplicates
+            set_caller(accounts.bob);
+            create(&mut contract, "tracked");
+            set_caller(accounts.alice);
+            contract.complete_todo(3).unwrap();
+            assert_eq!(create(&mut contract, "tracked"), 5);
+        }
+
+        #[ink::test]
+        fn dedupe_covers_renames_and_reopens() {
+            let mut contract = InkyTodo::new();
+            contract.set_dedupe(true).unwrap();
+            let original = create(&mut contract, "original");
+            let other = create(&mut contract, "other");
+
+            assert_eq!(
+                contract.edit_todo(other, Some("original".into()), None, None, None, None),
+                Err(Error::DuplicateTitle)
+            );
+
+            contract.cancel_todo(original).unwrap();
+            contract.edit_todo(other, Some("original".into()), None, None, None, None).unwrap();
+            assert_eq!(
+                contract.reopen_todo(original, "needed again".into()),
+                Err(Error::DuplicateTitle)
+            );
+
+            contract.edit_todo(other, Some("renamed".into()), None, None, None, None).unwrap();
+            assert_eq!(contract.reopen_todo(original, "needed again".into()), Ok(()));
+            assert_eq!(
+                contract.create_todo("original".into(), String::new(), None, Vec::new(), None),
+                Err(Error::DuplicateTitle)
+            );
+        }
+
+        #[ink::test]
+        fn query_combines_owner_and_status() {
+            let accounts = accounts();
+            let mut contract = InkyTodo::new();
+            let alice_pending = create(&mut contract, "alice pending");
+            let alice_done = create(&mut contract, "alice done");
+            contract.complete_todo(alice_done).unwrap();
+            set_caller(accounts.bob);
+            create(&mut contract, "bob pending");
+
+            let filter = TodoFilter {
+                owner: Some(accounts.alice),
+                status: Some(TodoStatus::Pending),
+                ..Default::default()
+            };
+            assert_eq!(ids_of(contract.query(filter, 1, 10)), vec![alice_pending]);
+
+            let high = TodoFilter {
+                owner: Some(accounts.alice),
+                priority: Some(Priority::High),
+                ..Default::default()
+            };
+            assert!(contract.query(high, 1, 10).is_empty());
+            assert_eq!(contract.query(TodoFilter::default(), 1, 10).len(), 3);
+        }
+
+        #[ink::test]
+        fn edits_and_batches_respect_length_caps() {
+            let mut contract = InkyTodo::new();
+            let todo_id = create(&mut contract, "short");
+            let title = "t".repeat(MAX_TITLE_LEN as usize);
+            let description = "d".repeat(MAX_DESCRIPTION_LEN as usize);
+
+            contract.edit_todo(todo_id, Some(title.clone()), Some(description.clone()), None, None, None).unwrap();
+            assert_eq!(
+                contract.edit_todo(todo_id, Some(title.clone() + "t"), None, None, None, None),
+                Err(Error::TitleTooLong)
+            );
+            assert_eq!(
+                contract.edit_todo(todo_id, None, Some(description.clone() + "d"), None, None, None),
+                Err(Error::DescriptionTooLong)
+            );
+            assert_eq!(contract.get_todo(todo_id).unwrap().title, title);
+
+            assert_eq!(contract.create_todos(vec![(title.clone(), description.clone())]), Ok(vec![2]));
+            assert_eq!(
+                contract.create_todos(vec![(title + "t", description)]),
+                Err(Error::TitleTooLong)
+            );
+        }
+
+        #[ink::test]
+        fn todos_are_stored_tagged_with_their_layout() {
+            let mut contract = InkyTodo::new();
+            let todo_id = create(&mut contract, "tagged");
+            let todo = contract.get_todo(todo_id).unwrap();
+
+            let stored = contract.todos.get(todo_id).unwrap();
+            assert!(stored.is_latest());
+            assert_eq!(stored, StoredTodo::V1(todo.clone()));
+
+            // Latest entries are left as they are
+            contract.migrate_todo(todo_id).unwrap();
+            assert_eq!(contract.get_todo(todo_id), Some(todo));
+            assert_eq!(contract.migrate_todo(99), Err(Error::TodoNotFound));
+        }
+
+        #[ink::test]
+        fn stored_todo_encoding_starts_with_its_layout_tag() {
+            let contract = InkyTodo::new();
+            let todo = contract.draft_todo("raw".into(), String::new());
+
+            // Later layouts are told apart by this leading byte, so `V1` must stay at index 0
+            let encoded = scale::Encode::encode(&StoredTodo::V1(todo.clone()));
+            assert_eq!(encoded[0], 0);
+            assert_eq!(encoded[1..], scale::Encode::encode(&todo)[..]);
+
+            let decoded = <StoredTodo as scale::Decode>::decode(&mut &encoded[..]).unwrap();
+            assert_eq!(Todo::from(decoded), todo);
+        }
+
+        #[ink::test]
+        fn clearing_my_todos_spares_other_accounts() {
+            let accounts = accounts();
+            let mut contract = InkyTodo::new();
+            for title in ["one", "two", "three"] {
+                create(&mut contract, title);
+            }
+            set_caller(accounts.bob);
+            let bobs = create(&mut contract, "bob's");
+
+            set_caller(accounts.alice);
+            assert_eq!(contract.delete_all_my_todos(false), Ok(0));
+            assert_eq!(contract.todo_count_of(accounts.alice), 3);
+            assert_eq!(contract.delete_all_my_todos(true), Ok(3));
+
+            assert!(contract.list_my_todos().is_empty());
+            assert_eq!(contract.todo_count_of(accounts.alice), 0);
+            assert_eq!(ids_of(contract.export_all(0, 10)), vec![bobs]);
+            assert_eq!(contract.todo_count_of(accounts.bob), 1);
+        }
+
+        #[ink::test]
+        fn clear_mine_works_in_batches() {
+            let mut contract = InkyTodo::new();
+            for index in 0..MAX_BATCH_SIZE + 2 {
+                create(&mut contract, &format!("todo {index}"));
+            }
+
+            assert_eq!(contract.clear_mine(), Ok((MAX_BATCH_SIZE, true)));
+            assert_eq!(contract.clear_mine(), Ok((2, false)));
+            assert_eq!(contract.count_todos(), 0);
+        }
+
+        #[ink::test]
+        fn todo_edited_reports_changed_fields() {
+            let mut contract = InkyTodo::new();
+            let todo_id = contract
+                .create_todo("title".into(), "description".into(), None, Vec::new(), None)
+                .unwrap();
+
+            contract.edit_todo(todo_id, Some("new title".into()), Some("description".into()), None, None, None).unwrap();
+            contract.edit_todo(todo_id, None, Some("new description".into()), None, None, None).unwrap();
+            contract.edit_todo(todo_id, None, None, Some(Priority::High), None, None).unwrap();
+
+            let edits = events_of::<TodoEdited>();
+            assert_eq!(edits.len(), 3);
+            assert_eq!((edits[0].title_changed, edits[0].desc_changed), (true, false));
+            assert_eq!((edits[1].title_changed, edits[1].desc_changed), (false, true));
+            assert_eq!((edits[2].title_changed, edits[2].desc_changed), (false, false));
+        }
+
+        #[ink::test]
+        fn no_op_edit_emits_nothing() {
+            let mut contract = InkyTodo::new();
+            let todo_id = contract
+                .create_todo("title".into(), "description".into(), None, Vec::new(), None)
+                .unwrap();
+            let before = contract.get_todo(todo_id).unwrap();
+            let recorded = ink::env::test::recorded_events().count();
+
+            contract
+                .edit_todo(todo_id, Some("title".into()), Some("description".into()), Some(Priority::Medium), None, None)
+                .unwrap();
+
+            assert_eq!(ink::env::test::recorded_events().count(), recorded);
+            assert_eq!(contract.get_todo(todo_id), Some(before));
+        }
+
+        #[ink::test]
+        fn stale_revision_is_rejected() {
+            let mut contract = InkyTodo::new();
+            let todo_id = create(&mut contract, "contended");
+            let read_rev = contract.get_todo(todo_id).unwrap().rev;
+
+            // Someone else writes in between
+            contract.set_priority(todo_id, Priority::High).unwrap();
+
+            assert_eq!(
+                contract.update_todo_status_checked(todo_id, TodoStatus::Completed, read_rev),
+                Err(Error::StaleRevision)
+            );
+            let current = contract.get_todo(todo_id).unwrap();
+            assert_eq!(current.status, TodoStatus::Pending);
+            assert_eq!(
+                contract.update_todo_status_checked(todo_id, TodoStatus::Completed, current.rev),
+                Ok(())
+            );
+        }
+
+        #[ink::test]
+        fn dependency_blocks_completion_until_done() {
+            let mut contract = InkyTodo::new();
+            let a = create(&mut contract, "A");
+            let b = create(&mut contract, "B");
+            contract.add_dependency(b, a).unwrap();
+            assert_eq!(contract.add_dependency(a, b), Err(Error::InvalidDependency));
+            assert_eq!(contract.add_dependency(a, a), Err(Error::InvalidDependency));
+
+            assert_eq!(contract.complete_todo(b), Err(Error::DependenciesIncomplete));
+            assert_eq!(contract.get_todo(b).unwrap().status, TodoStatus::Pending);
+
+            contract.complete_todo(a).unwrap();
+            assert_eq!(contract.complete_todo(b), Ok(()));
+        }
+
+        #[ink::test]
+        fn imported_todos_drop_their_dependencies() {
+            let mut contract = InkyTodo::new();
+            let a = create(&mut contract, "A");
+            let b = create(&mut contract, "B");
+            contract.add_dependency(b, a).unwrap();
+            let exported = contract.export_todos();
+
+            let imported = contract.import_todos(exported).unwrap();
+
+            assert!(contract.get_todo(imported[1]).unwrap().blocked_by.is_empty());
+            assert_eq!(contract.complete_todo(imported[1]), Ok(()));
+        }
+
+        #[ink::test]
+        fn peek_next_id_tracks_creation() {
+            let mut contract = InkyTodo::new();
+            assert_eq!(contract.peek_next_id(), 1);
+
+            let first = create(&mut contract, "first");
+            assert_eq!(first, 1);
+            assert_eq!(contract.peek_next_id(), 2);
+
+            create(&mut contract, "second");
+            assert_eq!(contract.peek_next_id(), 3);
+        }
+
+        #[ink::test]
+        fn completion_without_notifier_calls_nothing() {
+            let mut contract = InkyTodo::new();
+            assert_eq!(contract.notifier(), None);
+            let todo_id = create(&mut contract, "quiet");
+
+            contract.complete_todo(todo_id).unwrap();
+
+            assert!(events_of::<NotifyFailed>().is_empty());
+            assert_eq!(contract.locked.get(), None);
+        }
+
+        // Cross-contract calls don't run off-chain, so this only shows completion reaches
+        // the notifier call. A mock notifier deployed in an e2e test covers the callee side.
+        #[ink::test]
+        #[should_panic(expected = "does not support contract invocation")]
+        fn completion_calls_the_notifier() {
+            let mut contract = InkyTodo::new();
+            contract.set_notifier(Some(accounts().django)).unwrap();
+            let todo_id = create(&mut contract, "notified");
+
+            let _ = contract.complete_todo(todo_id);
+        }
+
+        #[ink::test]
+        fn search_my_todos_matches_title_or_description() {
+            let mut contract = InkyTodo::new();
+            let by_title = contract
+                .create_todo("Groceries".into(), "weekly shop".into(), None, Vec::new(), None)
+                .unwrap();
+            let by_description = contract
+                .create_todo("Errands".into(), "pick up groceries".into(), None, Vec::new(), None)
+                .unwrap();
+            let title_only = create(&mut contract, "Dentist");
+            set_caller(accounts().bob);
+            create(&mut contract, "groceries for bob");
+            set_caller(accounts().alice);
+
+            assert_eq!(ids_of(contract.search_my_todos("grocer".into())), vec![by_title, by_description]);
+            assert_eq!(ids_of(contract.search_my_todos("dent".into())), vec![title_only]);
+            assert_eq!(ids_of(contract.search_my_todos("pick up".into())), vec![by_description]);
+            assert!(contract.search_my_todos("gr".into()).is_empty());
+        }
+
+        #[ink::test]
+        fn create_skips_ids_already_taken() {
+            let mut contract = InkyTodo::new();
+            let mut planted = contract.draft_todo("planted".into(), String::new());
+            planted.id = 2;
+            contract.todos.insert(2, &StoredTodo::V1(planted.clone()));
+            assert_eq!(create(&mut contract, "first"), 1);
+
+            assert_eq!(create(&mut contract, "second"), 3);
+
+            assert_eq!(contract.get_todo(2), Some(planted));
+            assert_eq!(contract.peek_next_id(), 4);
+        }
+
+        #[ink::test]
+        fn create_never_reuses_a_deleted_id() {
+            let mut contract = InkyTodo::new();
+            let todo_id = create(&mut contract, "deleted");
+            contract.delete_todo(todo_id).unwrap();
+
+            // Even if the counter were rolled back, the tombstone keeps the ID retired
+            contract.next_id = todo_id;
+            assert_eq!(create(&mut contract, "fresh"), todo_id + 1);
+            assert_eq!(contract.deleted_info(todo_id).unwrap().0, "deleted");
+        }
+
+        #[ink::test]
+        fn todo_updated_carries_the_previous_status() {
+            let mut contract = InkyTodo::new();
+            assert_eq!(contract.version(), CONTRACT_VERSION);
+            let todo_id = create(&mut contract, "tracked");
+
+            contract.update_todo_status(todo_id, TodoStatus::InProgress).unwrap();
+            contract.complete_todo(todo_id).unwrap();
+            contract.reopen_todo(todo_id, "missed a step".into()).unwrap();
+
+            let transitions: Vec<_> = events_of::<TodoUpdated>()
+                .into_iter()
+                .map(|event| (event.previous_status, event.new_status))
+                .collect();
+            assert_eq!(
+                transitions,
+                vec![
+                    (TodoStatus::Pending, TodoStatus::InProgress),
+                    (TodoStatus::InProgress, TodoStatus::Completed),
+                    (TodoStatus::Completed, TodoStatus::Pending),
+                ]
+            );
+        }
+
+        #[ink::test]
+        fn in_progress_sits_between_pending_and_completed() {
+            let mut contract = InkyTodo::new();
+            let todo_id = create(&mut contract, "worked on");
+
+            contract.update_todo_status(todo_id, TodoStatus::InProgress).unwrap();
+            assert_eq!(contract.count_by_status(TodoStatus::InProgress), 1);
+            assert_eq!(contract.counts(), (1, 1));
+
+            contract.update_todo_status(todo_id, TodoStatus::Completed).unwrap();
+            assert_eq!(contract.count_by_status(TodoStatus::InProgress), 0);
+            assert_eq!(contract.count_by_status(TodoStatus::Completed), 1);
+
+            assert_eq!(
+                contract.update_todo_status(todo_id, TodoStatus::InProgress),
+                Err(Error::InvalidTransition)
+            );
+            assert_eq!(contract.get_todo(todo_id).unwrap().status, TodoStatus::Completed);
+        }
+
+        #[ink::test]
+        fn exists_tracks_the_todo_lifecycle() {
+            let mut contract = InkyTodo::new();
+            let todo_id = create(&mut contract, "exists");
+
+            assert!(contract.exists(todo_id));
+            assert!(!contract.exists(todo_id + 1));
+            assert!(!contract.exists(0));
+
+            contract.delete_todo(todo_id).unwrap();
+            assert!(!contract.exists(todo_id));
+        }
+
+        #[ink::test]
+        fn toggling_favorite_twice_unpins() {
+            let mut contract = InkyTodo::new();
+            create(&mut contract, "regular");
+            let pinned = create(&mut contract, "pinned");
+
+            assert_eq!(contract.toggle_favorite(pinned), Ok(true));
+            assert_eq!(ids_of(contract.list_favorites()), vec![pinned]);
+            assert_eq!(ids_of(contract.list_my_todos_ordered()), vec![pinned, 1]);
+
+            assert_eq!(contract.toggle_favorite(pinned), Ok(false));
+            assert!(contract.list_favorites().is_empty());
+            assert_eq!(ids_of(contract.list_my_todos_ordered()), vec![1, pinned]);
+
+            let favorited: Vec<bool> = events_of::<TodoFavorited>().into_iter().map(|event| event.favorite).collect();
+            assert_eq!(favorited, vec![true, false]);
+        }
+
+        #[ink::test]
+        fn active_limit_frees_up_on_completion() {
+            let mut contract = InkyTodo::new();
+            contract.set_max_active(Some(2)).unwrap();
+            let first = create(&mut contract, "first");
+            create(&mut contract, "second");
+
+            assert_eq!(
+                contract.create_todo("third".into(), String::new(), None, Vec::new(), None),
+                Err(Error::ActiveLimitReached)
+            );
+
+            contract.complete_todo(first).unwrap();
+            assert_eq!(create(&mut contract, "third"), 3);
+            assert_eq!(contract.counts(), (3, 2));
+        }
+
+        #[ink::test]
+        fn due_soon_returns_only_todos_inside_the_window() {
+            let mut contract = InkyTodo::new();
+            set_timestamp(10_000);
+            let due = |contract: &mut InkyTodo, title: &str, due_date| {
+                contract.create_todo(title.into(), String::new(), Some(due_date), Vec::new(), None).unwrap()
+            };
+            due(&mut contract, "past", 5_000);
+            let near = due(&mut contract, "near", 12_000);
+            due(&mut contract, "far", 100_000);
+
+            assert_eq!(ids_of(contract.due_soon(5_000)), vec![near]);
+        }
+
+        #[ink::test]
+        fn constructor_emits_one_instantiated_event() {
+            let accounts = accounts();
+
+            InkyTodo::new();
+
+            assert_eq!(ink::env::test::recorded_events().count(), 1);
+            let instantiated = events_of::<Instantiated>();
+            assert_eq!(instantiated.len(), 1);
+            assert_eq!(instantiated[0].owner, accounts.alice);
+        }
+
+        #[ink::test]
+        fn creation_fee_handles_under_exact_and_overpayment() {
+            let contract_account = use_contract_account();
+            let mut contract = InkyTodo::new();
+            contract.set_create_fee(10).unwrap();
+
+            ink::env::test::set_value_transferred::<Env>(3);
+            assert_eq!(
+                contract.create_todo_with_priority("underpaid".into(), String::new(), Priority::Low),
+                Err(Error::InsufficientFee)
+            );
+            ink::env::test::set_value_transferred::<Env>(0);
+
+            let exact = paying(10, || create(&mut contract, "exact"));
+            let overpaid = paying(25, || create(&mut contract, "overpaid"));
+
+            assert_eq!(contract.get_todo(exact).unwrap().stake, 0);
+            assert_eq!(contract.get_todo(overpaid).unwrap().stake, 15);
+            assert_eq!(contract.stakes(), (15, 0));
+            assert_eq!(balance_of(contract_account), 35);
+        }
+
+        #[ink::test]
+        fn last_n_returns_the_newest_survivors() {
+            let mut contract = InkyTodo::new();
+            for index in 1..=7 {
+                create(&mut contract, &format!("todo {index}"));
+            }
+
+            contract.delete_todo(6).unwrap();
+
+            assert_eq!(ids_of(contract.last_n(3)), vec![7, 5, 4]);
+            assert_eq!(ids_of(contract.last_n(100)), vec![7, 5, 4, 3, 2, 1]);
+        }
+
+        #[ink::test]
+        fn colors_must_fit_rgb() {
+            let mut contract = InkyTodo::new();
+
+            let todo_id = contract
+                .create_todo("colored".into(), String::new(), None, Vec::new(), Some(MAX_COLOR))
+                .unwrap();
+            assert_eq!(contract.get_todo(todo_id).unwrap().color, Some(MAX_COLOR));
+
+            assert_eq!(
+                contract.create_todo("too colorful".into(), String::new(), None, Vec::new(), Some(MAX_COLOR + 1)),
+                Err(Error::InvalidColor)
+            );
+            assert_eq!(
+                contract.edit_todo(todo_id, None, None, None, None, Some(0x0100_0000)),
+                Err(Error::InvalidColor)
+            );
+            assert_eq!(contract.get_todo(todo_id).unwrap().color, Some(MAX_COLOR));
+        }
+
+        #[ink::test]
+        fn time_locked_todo_completes_only_after_release() {
+            let mut contract = InkyTodo::new();
+            set_timestamp(1_000);
+            let todo_id = create(&mut contract, "locked");
+            contract.set_not_before(todo_id, Some(5_000)).unwrap();
+
+            set_timestamp(4_999);
+            assert_eq!(contract.complete_todo(todo_id), Err(Error::TooEarly));
+            assert_eq!(contract.get_todo(todo_id).unwrap().status, TodoStatus::Pending);
+
+            set_timestamp(5_000);
+            assert_eq!(contract.complete_todo(todo_id), Ok(()));
+        }
+
+        #[ink::test]
+        fn allowlist_gates_creation_only() {
+            let accounts = accounts();
+            let mut contract = InkyTodo::new();
+            contract.set_allowlist_enabled(true).unwrap();
+            contract.add_to_allowlist(accounts.bob).unwrap();
+            assert!(!contract.is_allowed(accounts.charlie));
+
+            set_caller(accounts.bob);
+            let todo_id = create(&mut contract, "allowed");
+
+            set_caller(accounts.alice);
+            contract.remove_from_allowlist(accounts.bob).unwrap();
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                contract.create_todo("not anymore".into(), String::new(), None, Vec::new(), None),
+                Err(Error::NotAllowed)
+            );
+            contract.set_priority(todo_id, Priority::High).unwrap();
+            contract.complete_todo(todo_id).unwrap();
+
+            set_caller(accounts.alice);
+            contract.set_allowlist_enabled(false).unwrap();
+            set_caller(accounts.charlie);
+            assert_eq!(create(&mut contract, "open again"), 2);
+        }
+
+        #[ink::test]
+        fn ownership_waits_for_acceptance() {
+            let accounts = accounts();
+            let mut contract = InkyTodo::new();
+
+            contract.propose_owner(accounts.bob).unwrap();
+            assert_eq!(contract.owner(), accounts.alice);
+            assert_eq!(contract.pending_owner(), Some(accounts.bob));
+            assert!(events_of::<OwnershipTransferred>().is_empty());
+
+            set_caller(accounts.eve);
+            assert_eq!(contract.accept_ownership(), Err(Error::NotPendingOwner));
+
+            set_caller(accounts.alice);
+            contract.cancel_ownership_transfer().unwrap();
+            set_caller(accounts.bob);
+            assert_eq!(contract.accept_ownership(), Err(Error::NotPendingOwner));
+            assert_eq!(contract.owner(), accounts.alice);
+        }
+
+        #[ink::test]
+        fn writes_are_rejected_while_locked() {
+            let mut contract = InkyTodo::new();
+            let todo_id = create(&mut contract, "guarded");
+
+            // State while the notifier call is in flight
+            contract.locked.set(&true);
+            assert_eq!(contract.complete_todo(todo_id), Err(Error::ReentrantCall));
+            assert_eq!(
+                contract.create_todo("re-entered".into(), String::new(), None, Vec::new(), None),
+                Err(Error::ReentrantCall)
+            );
+            assert_eq!(contract.delete_todo(todo_id), Err(Error::ReentrantCall));
+            assert_eq!(contract.get_todo(todo_id).unwrap().status, TodoStatus::Pending);
+
+            contract.locked.set(&false);
+            assert_eq!(contract.complete_todo(todo_id), Ok(()));
+        }
+
+        #[ink::test]
+        fn bulk_update_status_counts_only_applied_changes() {
+            let mut contract = InkyTodo::new();
+            let pending = create(&mut contract, "pending");
+            let completed = create(&mut contract, "completed");
+            contract.complete_todo(completed).unwrap();
+            let in_progress = create(&mut contract, "in progress");
+            contract.update_todo_status(in_progress, TodoStatus::InProgress).unwrap();
+            let deleted = create(&mut contract, "deleted");
+            contract.delete_todo(deleted).unwrap();
+
+            let ids = vec![pending, completed, deleted, 0, in_progress, pending];
+            assert_eq!(contract.bulk_update_status(ids, TodoStatus::InProgress), Ok(1));
+
+            assert_eq!(contract.get_todo(pending).unwrap().status, TodoStatus::InProgress);
+            assert_eq!(contract.get_todo(completed).unwrap().status, TodoStatus::Completed);
+            assert_eq!(contract.count_by_status(TodoStatus::InProgress), 2);
+            assert_eq!(events_of::<TodoUpdated>().len(), 3);
+        }
+
+        #[ink::test]
+        fn bulk_update_status_caps_the_id_list() {
+            let mut contract = InkyTodo::new();
+            let todo_id = create(&mut contract, "capped");
+
+            let ids = vec![todo_id; MAX_BATCH_SIZE as usize + 1];
+            assert_eq!(contract.bulk_update_status(ids, TodoStatus::Completed), Err(Error::BatchTooLarge));
+            assert_eq!(contract.get_todo(todo_id).unwrap().status, TodoStatus::Pending);
+        }
+
+        #[ink::test]
+        fn todo_view_keeps_only_public_fields() {
+            let accounts = accounts();
+            let mut contract = InkyTodo::new();
+            let todo_id = contract
+                .create_todo("public".into(), "private notes".into(), Some(9_000), vec!["tag".into()], Some(0x00FF00))
+                .unwrap();
+            contract.assign_todo(todo_id, accounts.bob).unwrap();
+            contract.toggle_favorite(todo_id).unwrap();
+            contract.update_todo_status(todo_id, TodoStatus::InProgress).unwrap();
+            let todo = contract.get_todo(todo_id).unwrap();
+            assert_eq!(todo.assignee, Some(accounts.bob));
+
+            assert_eq!(
+                contract.get_todo_view(todo_id),
+                Some(TodoView {
+                    id: todo_id,
+                    title: "public".into(),
+                    status: TodoStatus::InProgress,
+                    due_date: Some(9_000),
+                })
+            );
+            assert_eq!(TodoView::from(todo), contract.get_todo_view(todo_id).unwrap());
+            assert_eq!(contract.get_todo_view(99), None);
+        }
+
+        #[ink::test]
+        fn all_ids_pages_along_the_live_list() {
+            let mut contract = InkyTodo::new();
+            for title in ["one", "two", "three", "four", "five"] {
+                create(&mut contract, title);
+            }
+            contract.delete_todo(2).unwrap();
+
+            assert_eq!(contract.all_ids(0, 2), vec![1, 3]);
+            assert_eq!(contract.all_ids(3, 2), vec![4, 5]);
+            assert!(contract.all_ids(5, 2).is_empty());
+            // A cursor that is no longer live cannot be followed
+            assert!(contract.all_ids(2, 2).is_empty());
+            assert!(contract.all_ids(99, 2).is_empty());
+        }
+
+        #[ink::test]
+        fn search_title_reads_only_its_id_window() {
+            let mut contract = InkyTodo::new();
+            for title in ["milk one", "bread", "eggs", "milk two"] {
+                create(&mut contract, title);
+            }
+
+            // The window 1..3 holds one match; the later one needs the next page
+            assert_eq!(ids_of(contract.search_title("milk".into(), 1, 2)), vec![1]);
+            assert!(contract.search_title("milk".into(), 2, 2).is_empty());
+            assert_eq!(ids_of(contract.search_title("milk".into(), 3, 2)), vec![4]);
+        }
+
+        #[ink::test]
+        fn query_stops_at_the_end_of_its_window() {
+            let accounts = accounts();
+            let mut contract = InkyTodo::new();
+            for title in ["one", "two", "three"] {
+                create(&mut contract, title);
+            }
+            set_caller(accounts.bob);
+            let bobs = create(&mut contract, "bob's");
+
+            let filter = TodoFilter { owner: Some(accounts.bob), ..Default::default() };
+            // Bob's todo lies past the window, so the first page is empty rather than read on
+            assert!(contract.query(filter.clone(), 1, 3).is_empty());
+            assert_eq!(ids_of(contract.query(filter, 4, 3)), vec![bobs]);
+        }
+
+        #[ink::test]
+        fn max_total_is_owner_set_and_caps_live_todos() {
+            let accounts = accounts();
+            let mut contract = InkyTodo::new();
+            assert_eq!(contract.max_total(), None);
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.set_max_total(Some(2)), Err(Error::NotContractOwner));
+
+            set_caller(accounts.alice);
+            contract.set_max_total(Some(2)).unwrap();
+            create(&mut contract, "one");
+            create(&mut contract, "two");
+            assert_eq!(
+                contract.create_todo("three".into(), String::new(), None, Vec::new(), None),
+                Err(Error::TooManyTodos)
+            );
+
+            contract.set_max_total(None).unwrap();
+            create(&mut contract, "three");
+        }
+
+        #[ink::test]
+        fn status_listing_and_export_page_through_bounded_windows() {
+            let mut contract = InkyTodo::new();
+            for title in ["one", "two", "three", "four"] {
+                create(&mut contract, title);
+            }
+            contract.complete_todo(1).unwrap();
+            contract.complete_todo(4).unwrap();
+
+            assert_eq!(ids_of(contract.get_todos_by_status(TodoStatus::Completed, 1, 2)), vec![1]);
+            assert_eq!(ids_of(contract.get_todos_by_status(TodoStatus::Completed, 3, 2)), vec![4]);
+
+            assert_eq!(ids_of(contract.export_all(0, 3)), vec![1, 2, 3]);
+            assert_eq!(ids_of(contract.export_all(3, 3)), vec![4]);
+        }
+
+        #[ink::test]
+        fn create_todos_batch_matches_create_todos() {
+            let mut contract = InkyTodo::new();
+            let items = vec![("one".to_string(), String::new()), ("two".to_string(), "second".to_string())];
+
+            assert_eq!(contract.create_todos_batch(items), Ok(vec![1, 2]));
+            assert_eq!(contract.get_todo(2).unwrap().description, "second");
+            assert_eq!(
+                contract.create_todos_batch(vec![(String::new(), String::new())]),
+                Err(Error::TitleEmpty)
+            );
+            assert_eq!(contract.count_todos(), 2);
+        }
+
+        #[ink::test]
+        fn set_max_todos_rejects_limits_above_the_ceiling() {
+            let mut contract = InkyTodo::new();
+
+            assert_eq!(contract.set_max_todos(MAX_TODOS_PER_ACCOUNT + 1), Err(Error::LimitTooHigh));
+            assert_eq!(contract.set_max_todos(MAX_TODOS_PER_ACCOUNT), Ok(()));
+
+            // A full owner index still fits in a single storage value
+            let ids: Vec<u32> = (1..=MAX_TODOS_PER_ACCOUNT).collect();
+            assert!(scale::Encode::encoded_size(&ids) < 16 * 1024);
+        }
+
+        #[ink::test]
+        fn contract_owner_deleting_a_todo_refunds_its_stake() {
+            let accounts = accounts();
+            let contract_account = use_contract_account();
+            let mut contract = InkyTodo::new();
+
+            set_caller(accounts.bob);
+            let bob_before = balance_of(accounts.bob);
+            let todo_id = paying(40, || {
+                contract.create_todo("bob's".into(), String::new(), None, Vec::new(), None)
+            })
+            .unwrap();
+
+            set_caller(accounts.alice);
+            contract.delete_todo(todo_id).unwrap();
+
+            assert_eq!(contract.stakes(), (0, 0));
+            assert_eq!(balance_of(contract_account), 0);
+            assert_eq!(balance_of(accounts.bob), bob_before);
+        }
+
+        #[ink::test]
+        fn editor_cancelling_a_todo_refunds_its_stake() {
+            let accounts = accounts();
+            use_contract_account();
+            let mut contract = InkyTodo::new();
+            contract.grant_editor(accounts.charlie).unwrap();
+
+            set_caller(accounts.bob);
+            let bob_before = balance_of(accounts.bob);
+            let todo_id = paying(25, || {
+                contract.create_todo("bob's".into(), String::new(), None, Vec::new(), None)
+            })
+            .unwrap();
+
+            set_caller(accounts.charlie);
+            contract.cancel_todo(todo_id).unwrap();
+
+            assert_eq!(contract.stakes(), (0, 0));
+            assert_eq!(balance_of(accounts.bob), bob_before);
+        }
+
+        #[ink::test]
+        fn sweeping_someone_elses_todo_refunds_its_stake() {
+            let accounts = accounts();
+            use_contract_account();
+            let mut contract = InkyTodo::new();
+
+            set_caller(accounts.bob);
+            let bob_before = balance_of(accounts.bob);
+            let late = paying(30, || {
+                contract.create_todo("late".into(), String::new(), Some(1_000), Vec::new(), None)
+            })
+            .unwrap();
+            let own = paying(20, || {
+                contract.create_todo("own".into(), String::new(), Some(1_000), Vec::new(), None)
+            })
+            .unwrap();
+
+            set_timestamp(2_000);
+            set_caller(accounts.charlie);
+            // Sweep only `late`, leaving `own` for its owner to sweep below
+            assert_eq!(contract.sweep_overdue(late, 1), Ok(1));
+            assert_eq!(contract.stakes(), (20, 0));
+            assert_eq!(balance_of(accounts.bob), bob_before - 20);
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.sweep_overdue(own, 1), Ok(1));
+            assert_eq!(contract.stakes(), (0, 20));
+        }
+
+        #[ink::test]
+        fn empty_batches_are_rejected_with_their_payment() {
+            use_contract_account();
+            let mut contract = InkyTodo::new();
+
+            assert_eq!(paying(10, || contract.create_todos(Vec::new())), Err(Error::EmptyBatch));
+            assert_eq!(paying(10, || contract.import_todos(Vec::new())), Err(Error::EmptyBatch));
+            assert_eq!(contract.count_todos(), 0);
+        }
+
+        #[ink::test]
+        fn withdrawing_keeps_the_forfeited_total() {
+            let accounts = accounts();
+            use_contract_account();
+            let mut contract = InkyTodo::new();
+            let todo_id = paying(50, || {
+                contract.create_todo("staked".into(), String::new(), None, Vec::new(), None)
+            })
+            .unwrap();
+            contract.cancel_todo(todo_id).unwrap();
+            assert_eq!(contract.stakes(), (0, 50));
+
+            contract.withdraw(50, accounts.django).unwrap();
+
+            assert_eq!(contract.stakes(), (0, 50));
+            assert_eq!(balance_of(accounts.django), 50);
+        }
+
+        #[ink::test]
+        fn archived_todos_are_never_overdue() {
+            let mut contract = InkyTodo::new();
+            let todo_id = contract
+                .create_todo("shelved".into(), String::new(), Some(1_000), Vec::new(), None)
+                .unwrap();
+            contract.archive_todo(todo_id).unwrap();
+            set_timestamp(2_000);
+
+            assert_eq!(contract.is_overdue(todo_id), Ok(false));
+            assert_eq!(contract.sweep_overdue(1, 10), Ok(0));
+            assert_eq!(contract.get_todo(todo_id).unwrap().status, TodoStatus::Pending);
+            assert_eq!(contract.get_statistics().overdue, 0);
+        }
     }
 }
\ No newline at end of file